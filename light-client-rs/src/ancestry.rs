@@ -0,0 +1,111 @@
+//! Ancestry proofs, extending a finalized header to the blocks before it.
+//!
+//! The state of a block keeps the roots of the last `SLOTS_PER_HISTORICAL_ROOT` blocks in
+//! `block_roots`. Since Capella, the root of each full `block_roots` vector is appended to
+//! `historical_summaries` at the end of its period, which reaches every block from Capella on.
+//! Blocks before Capella are only covered by the frozen `historical_roots` and aren't supported.
+
+use super::types::*;
+use super::utils::*;
+use crate::chain_spec::ChainSpec;
+use crate::error::{LightClientError, MerkleBranch};
+use crate::merkle::concat_generalized_indices;
+use alloc::vec::Vec;
+use crate::hasher::Hasher;
+
+/// How a historical block root is committed to in the state of the finalized header.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AncestryProof {
+    /// For blocks among the last `SLOTS_PER_HISTORICAL_ROOT` before the finalized one, the branch
+    /// from the block root through `state.block_roots` to the state root.
+    BlockRoots { block_roots_branch: Vec<H256> },
+    /// For older blocks, the branch from the block root to the `block_roots` root of its period,
+    /// which is the `block_summary_root` of its historical summary, and the branch from there
+    /// through `state.historical_summaries` to the state root.
+    HistoricalSummaries {
+        block_roots_branch: Vec<H256>,
+        block_summary_root: H256,
+        historical_summary_branch: Vec<H256>,
+    },
+}
+
+/// Verifies that `historical_header` is an ancestor of `finalized_header`, so that it is final
+/// too. A block root only proves the header it was computed from, the execution payload of the
+/// historical header still has to be checked against its body root before it is used.
+pub fn verify_ancestry<H: Hasher>(
+    finalized_header: &LightClientHeader,
+    historical_header: &LightClientHeader,
+    proof: &AncestryProof,
+    spec: &ChainSpec<H>,
+) -> Result<(), LightClientError> {
+    let finalized_slot = finalized_header.beacon.slot;
+    let ancestor_slot = historical_header.beacon.slot;
+    let invalid_slot = LightClientError::InvalidAncestorSlot {
+        finalized_slot,
+        ancestor_slot,
+    };
+    // Neither proof can reach the finalized block itself or a later one.
+    if ancestor_slot >= finalized_slot {
+        return Err(invalid_slot);
+    }
+    let block_root = historical_header.beacon.hash_tree_root_with::<H>();
+    let block_roots_index = SLOTS_PER_HISTORICAL_ROOT + ancestor_slot % SLOTS_PER_HISTORICAL_ROOT;
+
+    match proof {
+        AncestryProof::BlockRoots { block_roots_branch } => {
+            if finalized_slot - ancestor_slot > SLOTS_PER_HISTORICAL_ROOT {
+                return Err(invalid_slot);
+            }
+            let gindex = concat_generalized_indices(&[
+                block_roots_gindex(spec, finalized_slot),
+                block_roots_index,
+            ]);
+            verify_header::<H>(
+                block_root,
+                block_roots_branch.iter().copied(),
+                finalized_header.beacon.state_root,
+                gindex,
+                MerkleBranch::BlockRoots,
+            )
+        }
+        AncestryProof::HistoricalSummaries {
+            block_roots_branch,
+            block_summary_root,
+            historical_summary_branch,
+        } => {
+            // Saturating, as networks without Capella schedule it at the far future epoch.
+            let capella_period =
+                spec.capella.epoch.saturating_mul(SLOTS_PER_EPOCH) / SLOTS_PER_HISTORICAL_ROOT;
+            let period = ancestor_slot / SLOTS_PER_HISTORICAL_ROOT;
+            // The summary of a period is only appended once the period is over.
+            let period_end = (period + 1)
+                .checked_mul(SLOTS_PER_HISTORICAL_ROOT)
+                .ok_or_else(|| invalid_slot.clone())?;
+            if period < capella_period || period_end > finalized_slot {
+                return Err(invalid_slot);
+            }
+            verify_header::<H>(
+                block_root,
+                block_roots_branch.iter().copied(),
+                *block_summary_root,
+                block_roots_index,
+                MerkleBranch::BlockRoots,
+            )?;
+            // The list's data root is the left child of its root, the right one is its length.
+            // Each summary is a `block_summary_root` and a `state_summary_root`.
+            let gindex = concat_generalized_indices(&[
+                historical_summaries_gindex(spec, finalized_slot),
+                2,
+                HISTORICAL_ROOTS_LIMIT + (period - capella_period),
+                2,
+            ]);
+            verify_header::<H>(
+                *block_summary_root,
+                historical_summary_branch.iter().copied(),
+                finalized_header.beacon.state_root,
+                gindex,
+                MerkleBranch::HistoricalSummary,
+            )
+        }
+    }
+}
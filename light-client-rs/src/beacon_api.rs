@@ -0,0 +1,128 @@
+//! Deserialization of the beacon node API's JSON encoding, enabled by the `serde` feature.
+//!
+//! The light client endpoints (`/eth/v1/beacon/light_client/updates`, `/bootstrap`,
+//! `/finality_update` and `/optimistic_update`) return the containers of the spec with byte
+//! strings as `0x` prefixed hex and integers as quoted decimals. Each response is wrapped in a
+//! [`Versioned`] envelope naming the fork of its container, the update types decode any fork's
+//! shape since the fork only decides which fields of a header are present.
+
+use super::types::*;
+use crate::chain_spec::ForkName;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use serde::de::{self, Deserialize, Deserializer, Error, Visitor};
+use ssz_rs::Deserialize as _;
+
+/// A response of the beacon API together with the fork its `data` belongs to.
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct Versioned<T> {
+    pub version: ForkName,
+    pub data: T,
+}
+
+impl<'de> Deserialize<'de> for H256 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        from_hex_to_array(deserializer).map(H256)
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        from_hex_to_array(deserializer).map(PublicKey)
+    }
+}
+
+pub(crate) fn from_hex_to_bytes<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<u8>, D::Error> {
+    let hex = String::deserialize(deserializer)?;
+    decode_hex(&hex).ok_or_else(|| D::Error::custom("invalid hex string"))
+}
+
+pub(crate) fn from_hex_to_array<'de, D: Deserializer<'de>, const N: usize>(
+    deserializer: D,
+) -> Result<[u8; N], D::Error> {
+    let bytes = from_hex_to_bytes(deserializer)?;
+    let length = bytes.len();
+    bytes
+        .try_into()
+        .map_err(|_| D::Error::invalid_length(length, &"a fixed size byte string"))
+}
+
+pub(crate) fn from_hex_to_bitvector<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Bitvector<SYNC_COMMITTEE_SIZE>, D::Error> {
+    let bytes = from_hex_to_bytes(deserializer)?;
+    Bitvector::<SYNC_COMMITTEE_SIZE>::deserialize(&bytes)
+        .map_err(|_| D::Error::custom("invalid sync committee bits"))
+}
+
+/// Integers are quoted in the beacon API, plain numbers are accepted as well.
+pub(crate) fn from_quoted_u64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    struct QuotedU64;
+
+    impl<'de> Visitor<'de> for QuotedU64 {
+        type Value = u64;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a quoted unsigned integer")
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<u64, E> {
+            Ok(value)
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<u64, E> {
+            value.parse().map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))
+        }
+    }
+
+    deserializer.deserialize_any(QuotedU64)
+}
+
+/// Decodes a quoted decimal uint256 into its little endian SSZ form.
+pub(crate) fn from_quoted_u256<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<[u8; 32], D::Error> {
+    let decimal = String::deserialize(deserializer)?;
+    if decimal.is_empty() {
+        return Err(D::Error::custom("empty uint256"));
+    }
+    let mut value = [0u8; 32];
+    for digit in decimal.bytes() {
+        if !digit.is_ascii_digit() {
+            return Err(D::Error::custom("invalid uint256"));
+        }
+        // value = value * 10 + digit, limb by limb
+        let mut carry = (digit - b'0') as u16;
+        for limb in value.iter_mut() {
+            let product = *limb as u16 * 10 + carry;
+            *limb = product as u8;
+            carry = product >> 8;
+        }
+        if carry != 0 {
+            return Err(D::Error::custom("uint256 overflow"));
+        }
+    }
+    Ok(value)
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex).as_bytes();
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    hex.chunks_exact(2)
+        .map(|pair| Some(nibble(pair[0])? << 4 | nibble(pair[1])?))
+        .collect()
+}
+
+fn nibble(digit: u8) -> Option<u8> {
+    match digit {
+        b'0'..=b'9' => Some(digit - b'0'),
+        b'a'..=b'f' => Some(digit - b'a' + 10),
+        b'A'..=b'F' => Some(digit - b'A' + 10),
+        _ => None,
+    }
+}
@@ -0,0 +1,178 @@
+//! Aggregation of sync committee pubkeys for signature verification.
+//!
+//! The curve arithmetic is provided by a [`BlsVerifier`] backend, selected with the `milagro`
+//! (default, `no_std`) or `blst` (fast native) cargo feature. When both are enabled
+//! [`DefaultBlsVerifier`] is the blst one.
+//!
+//! Decompressing a pubkey is by far the most expensive step of verifying a sync aggregate. A
+//! [`PreparedSyncCommittee`] keeps the decompressed points of a committee so callers that verify
+//! many updates against the same committee only pay for it once. When more than half of the
+//! committee signed, the participant aggregate is derived from the committee's
+//! `aggregate_pubkey` by subtracting the non-participants, which touches far fewer points.
+
+use super::types::*;
+use crate::error::{BlsError, LightClientError};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+#[cfg(feature = "blst")]
+mod blst;
+#[cfg(feature = "milagro")]
+mod milagro;
+
+#[cfg(feature = "blst")]
+pub use self::blst::BlstVerifier;
+#[cfg(feature = "milagro")]
+pub use self::milagro::MilagroVerifier;
+
+#[cfg(feature = "blst")]
+pub type DefaultBlsVerifier = BlstVerifier;
+#[cfg(all(feature = "milagro", not(feature = "blst")))]
+pub type DefaultBlsVerifier = MilagroVerifier;
+
+#[cfg(not(any(feature = "milagro", feature = "blst")))]
+compile_error!("one of the `milagro` or `blst` features has to be enabled");
+
+/// The BLS operations needed to verify a sync committee signature.
+pub trait BlsVerifier {
+    type PublicKey: Clone + Debug;
+    type AggregatePublicKey;
+
+    /// Decompresses a pubkey without a subgroup check, committee members are validated by the
+    /// beacon chain before they can join a committee.
+    fn decompress(pubkey: &PublicKey) -> Result<Self::PublicKey, BlsError>;
+
+    fn negate(pubkey: &Self::PublicKey) -> Result<Self::PublicKey, BlsError>;
+
+    fn aggregate(pubkeys: &[&Self::PublicKey]) -> Result<Self::AggregatePublicKey, BlsError>;
+
+    /// Verifies `signature` over `message` by the signers summed up in `aggregate_pubkey`.
+    fn fast_aggregate_verify(
+        aggregate_pubkey: &Self::AggregatePublicKey,
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), BlsError>;
+}
+
+/// A sync committee together with the decompressed points of its pubkeys.
+#[derive(Clone, Debug)]
+pub struct PreparedSyncCommittee<V: BlsVerifier = DefaultBlsVerifier> {
+    sync_committee: SyncCommittee,
+    // `None` for points that haven't been decompressed, see `PreparedSyncCommittee::lazy`.
+    pubkeys: Vec<Option<V::PublicKey>>,
+    aggregate_pubkey: Option<V::PublicKey>,
+}
+
+impl<V: BlsVerifier> PreparedSyncCommittee<V> {
+    /// Decompresses every pubkey of the committee up front.
+    pub fn new(sync_committee: SyncCommittee) -> Result<Self, LightClientError> {
+        let pubkeys = sync_committee
+            .pubkeys
+            .iter()
+            .map(|pubkey| V::decompress(pubkey).map(Some))
+            .collect::<Result<Vec<_>, _>>()?;
+        let aggregate_pubkey = Some(V::decompress(&sync_committee.aggregate_pubkey)?);
+        Ok(PreparedSyncCommittee {
+            sync_committee,
+            pubkeys,
+            aggregate_pubkey,
+        })
+    }
+
+    /// Wraps a committee that is used for a single verification, points are decompressed on
+    /// demand and only the ones the aggregation needs.
+    pub(crate) fn lazy(sync_committee: SyncCommittee) -> Self {
+        let pubkeys = sync_committee.pubkeys.iter().map(|_| None).collect();
+        PreparedSyncCommittee {
+            sync_committee,
+            pubkeys,
+            aggregate_pubkey: None,
+        }
+    }
+
+    pub fn sync_committee(&self) -> &SyncCommittee {
+        &self.sync_committee
+    }
+
+    /// Aggregates the pubkeys of the members whose bit is set.
+    pub(crate) fn aggregate_participants(
+        &self,
+        sync_committee_bits: &[u8],
+    ) -> Result<V::AggregatePublicKey, LightClientError> {
+        aggregate_participants::<V>(
+            sync_committee_bits.len(),
+            |index| sync_committee_bits[index] == 1,
+            |index| self.point(index),
+            || self.aggregate_point(),
+        )
+    }
+
+    fn point(&self, index: usize) -> Result<V::PublicKey, LightClientError> {
+        match self.pubkeys.get(index) {
+            Some(Some(pubkey)) => Ok(pubkey.clone()),
+            Some(None) => Ok(V::decompress(&self.sync_committee.pubkeys[index])?),
+            None => Err(BlsError::InvalidPublicKey.into()),
+        }
+    }
+
+    fn aggregate_point(&self) -> Result<V::PublicKey, LightClientError> {
+        match &self.aggregate_pubkey {
+            Some(pubkey) => Ok(pubkey.clone()),
+            None => Ok(V::decompress(&self.sync_committee.aggregate_pubkey)?),
+        }
+    }
+}
+
+impl<V: BlsVerifier> From<PreparedSyncCommittee<V>> for SyncCommittee {
+    fn from(value: PreparedSyncCommittee<V>) -> Self {
+        value.sync_committee
+    }
+}
+
+impl SyncCommittee {
+    /// Same as [`PreparedSyncCommittee::aggregate_participants`] of a lazily prepared committee,
+    /// for a committee that is only borrowed.
+    pub(crate) fn aggregate_participants<V: BlsVerifier>(
+        &self,
+        sync_committee_bits: &[u8],
+    ) -> Result<V::AggregatePublicKey, LightClientError> {
+        aggregate_participants::<V>(
+            sync_committee_bits.len(),
+            |index| sync_committee_bits[index] == 1,
+            |index| {
+                let pubkey = self.pubkeys.get(index).ok_or(BlsError::InvalidPublicKey)?;
+                Ok(V::decompress(pubkey)?)
+            },
+            || Ok(V::decompress(&self.aggregate_pubkey)?),
+        )
+    }
+}
+
+/// Aggregates the pubkeys of the members of a `committee_size` committee for which
+/// `is_participant` holds. `point` and `aggregate_point` provide the decompressed pubkeys of a
+/// member and of the whole committee, each one is only asked for when the aggregation needs it.
+pub(crate) fn aggregate_participants<V: BlsVerifier>(
+    committee_size: usize,
+    is_participant: impl Fn(usize) -> bool,
+    point: impl Fn(usize) -> Result<V::PublicKey, LightClientError>,
+    aggregate_point: impl FnOnce() -> Result<V::PublicKey, LightClientError>,
+) -> Result<V::AggregatePublicKey, LightClientError> {
+    let participants = (0..committee_size).filter(|index| is_participant(*index)).count();
+    let pubkeys = if participants * 2 > committee_size {
+        // aggregate_pubkey is the sum of all members, so adding the negated members that didn't
+        // sign leaves exactly the sum of the participants.
+        let mut pubkeys = vec![aggregate_point()?];
+        for index in (0..committee_size).filter(|index| !is_participant(*index)) {
+            pubkeys.push(V::negate(&point(index)?)?);
+        }
+        pubkeys
+    } else {
+        (0..committee_size)
+            .filter(|index| is_participant(*index))
+            .map(point)
+            .collect::<Result<Vec<_>, _>>()?
+    };
+    let pubkeys = pubkeys.iter().collect::<Vec<_>>();
+    Ok(V::aggregate(&pubkeys)?)
+}
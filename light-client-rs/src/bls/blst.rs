@@ -0,0 +1,86 @@
+//! Backend on top of the assembly optimized `blst` library, for native builds like the CLI and
+//! the relayer.
+
+use super::BlsVerifier;
+use crate::error::BlsError;
+use crate::types::PublicKey;
+use blst::min_pk::Signature;
+use blst::{
+    blst_p1, blst_p1_add_or_double_affine, blst_p1_affine, blst_p1_affine_serialize,
+    blst_p1_cneg, blst_p1_from_affine, blst_p1_to_affine, blst_p1_uncompress, BLST_ERROR,
+};
+
+// Domain separation tag of the Ethereum proof of possession scheme.
+const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+// Flag of the compressed encoding, see the zcash serialization format of BLS12-381 points.
+const COMPRESSION_FLAG: u8 = 0x80;
+
+// The safe wrappers of `blst::min_pk` hide their points, so the backend works on the raw ones to
+// negate and sum them and only wraps the final aggregate for the signature check.
+#[derive(Clone, Copy, Debug)]
+pub struct BlstVerifier;
+
+impl BlsVerifier for BlstVerifier {
+    type PublicKey = blst_p1_affine;
+    type AggregatePublicKey = blst_p1;
+
+    fn decompress(pubkey: &PublicKey) -> Result<Self::PublicKey, BlsError> {
+        if pubkey.0[0] & COMPRESSION_FLAG == 0 {
+            return Err(BlsError::InvalidPublicKey);
+        }
+        let mut point = blst_p1_affine::default();
+        match unsafe { blst_p1_uncompress(&mut point, pubkey.0.as_ptr()) } {
+            BLST_ERROR::BLST_SUCCESS => Ok(point),
+            BLST_ERROR::BLST_POINT_NOT_ON_CURVE | BLST_ERROR::BLST_POINT_NOT_IN_GROUP => {
+                Err(BlsError::InvalidPublicKeyPoint)
+            }
+            _ => Err(BlsError::InvalidPublicKey),
+        }
+    }
+
+    fn negate(pubkey: &Self::PublicKey) -> Result<Self::PublicKey, BlsError> {
+        let mut point = blst_p1::default();
+        let mut negated = blst_p1_affine::default();
+        unsafe {
+            blst_p1_from_affine(&mut point, pubkey);
+            blst_p1_cneg(&mut point, true);
+            blst_p1_to_affine(&mut negated, &point);
+        }
+        Ok(negated)
+    }
+
+    fn aggregate(pubkeys: &[&Self::PublicKey]) -> Result<Self::AggregatePublicKey, BlsError> {
+        let (first, rest) = pubkeys
+            .split_first()
+            .ok_or(BlsError::InvalidAggregatePublicKeys)?;
+        let mut sum = blst_p1::default();
+        unsafe {
+            blst_p1_from_affine(&mut sum, *first);
+            for pubkey in rest {
+                blst_p1_add_or_double_affine(&mut sum, &sum, *pubkey);
+            }
+        }
+        Ok(sum)
+    }
+
+    fn fast_aggregate_verify(
+        aggregate_pubkey: &Self::AggregatePublicKey,
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), BlsError> {
+        let signature = Signature::from_bytes(signature).map_err(|_| BlsError::InvalidSignature)?;
+        let mut point = blst_p1_affine::default();
+        let mut serialized = [0u8; 96];
+        unsafe {
+            blst_p1_to_affine(&mut point, aggregate_pubkey);
+            blst_p1_affine_serialize(serialized.as_mut_ptr(), &point);
+        }
+        let pubkey = blst::min_pk::PublicKey::deserialize(&serialized)
+            .map_err(|_| BlsError::InvalidAggregatePublicKeys)?;
+        match signature.fast_aggregate_verify_pre_aggregated(true, message, DST, &pubkey) {
+            BLST_ERROR::BLST_SUCCESS => Ok(()),
+            _ => Err(BlsError::VerificationFailed),
+        }
+    }
+}
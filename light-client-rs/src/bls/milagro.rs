@@ -0,0 +1,45 @@
+//! Pure Rust backend that builds for `no_std` targets such as the MIPS verifier.
+
+use super::BlsVerifier;
+use crate::error::BlsError;
+use crate::types::PublicKey;
+use milagro_bls::{AggregatePublicKey, AggregateSignature, AmclError, Signature};
+
+#[derive(Clone, Copy, Debug)]
+pub struct MilagroVerifier;
+
+impl BlsVerifier for MilagroVerifier {
+    type PublicKey = milagro_bls::PublicKey;
+    type AggregatePublicKey = AggregatePublicKey;
+
+    fn decompress(pubkey: &PublicKey) -> Result<Self::PublicKey, BlsError> {
+        milagro_bls::PublicKey::from_bytes_unchecked(&pubkey.0).map_err(|e| match e {
+            AmclError::InvalidPoint => BlsError::InvalidPublicKeyPoint,
+            _ => BlsError::InvalidPublicKey,
+        })
+    }
+
+    fn negate(pubkey: &Self::PublicKey) -> Result<Self::PublicKey, BlsError> {
+        let mut negated = pubkey.clone();
+        negated.point.neg();
+        Ok(negated)
+    }
+
+    fn aggregate(pubkeys: &[&Self::PublicKey]) -> Result<Self::AggregatePublicKey, BlsError> {
+        AggregatePublicKey::aggregate(pubkeys).map_err(|_| BlsError::InvalidAggregatePublicKeys)
+    }
+
+    fn fast_aggregate_verify(
+        aggregate_pubkey: &Self::AggregatePublicKey,
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), BlsError> {
+        let signature = Signature::from_bytes(signature).map_err(|_| BlsError::InvalidSignature)?;
+        let signature = AggregateSignature::from_signature(&signature);
+        if signature.fast_aggregate_verify_pre_aggregated(message, aggregate_pubkey) {
+            Ok(())
+        } else {
+            Err(BlsError::VerificationFailed)
+        }
+    }
+}
@@ -0,0 +1,35 @@
+//! Initializing a [`LightClientStore`] from a trusted checkpoint.
+//!
+//! The block root is expected to come from a weak subjectivity checkpoint obtained out of band,
+//! everything else in the bootstrap is checked against it.
+
+use super::types::*;
+use super::utils::*;
+use crate::chain_spec::ChainSpec;
+use crate::error::{LightClientError, MerkleBranch};
+use crate::store::LightClientStore;
+use crate::hasher::Hasher;
+
+pub fn initialize_from_bootstrap<H: Hasher>(
+    trusted_block_root: H256,
+    bootstrap: LightClientBootstrap,
+    spec: &ChainSpec<H>,
+) -> Result<LightClientStore, LightClientError> {
+    verify_light_client_header(&bootstrap.header, spec)?;
+
+    let block_root = bootstrap.header.beacon.hash_tree_root_with::<H>();
+    if block_root != trusted_block_root {
+        return Err(LightClientError::UntrustedBootstrapHeader);
+    }
+
+    let gindex = current_sync_committee_gindex(spec, bootstrap.header.beacon.slot);
+    verify_sync_committee::<H>(
+        &bootstrap.current_sync_committee,
+        bootstrap.current_sync_committee_branch,
+        bootstrap.header.beacon.state_root,
+        gindex,
+        MerkleBranch::CurrentSyncCommittee,
+    )?;
+
+    LightClientStore::new(bootstrap.header, bootstrap.current_sync_committee)
+}
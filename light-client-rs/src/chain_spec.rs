@@ -0,0 +1,269 @@
+//! Network parameters needed to verify sync committee signatures.
+//!
+//! The signing domain of a sync committee signature commits to the fork version active at the
+//! signature slot and to the genesis validators root, so both have to come from the network the
+//! update was produced on rather than from the update itself.
+//!
+//! The spec also carries the [`Hasher`] the network's SSZ objects are merkleized with, so that
+//! every verification function taking it is generic over the hasher without naming it.
+
+use super::types::*;
+use super::utils::compute_epoch_at_slot;
+use crate::hasher::{DefaultHasher, Hasher};
+use core::fmt;
+use core::marker::PhantomData;
+
+pub const FAR_FUTURE_EPOCH: u64 = u64::MAX;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize), serde(rename_all = "lowercase"))]
+pub enum ForkName {
+    Phase0,
+    Altair,
+    Bellatrix,
+    Capella,
+    Deneb,
+    Electra,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Fork {
+    pub version: ForkVersion,
+    pub epoch: u64,
+}
+
+pub struct ChainSpec<H = DefaultHasher> {
+    pub genesis_validators_root: H256,
+    pub genesis_time: u64,
+    pub genesis_fork_version: ForkVersion,
+    pub altair: Fork,
+    pub bellatrix: Fork,
+    pub capella: Fork,
+    pub deneb: Fork,
+    pub electra: Fork,
+    pub hasher: PhantomData<fn() -> H>,
+}
+
+// The networks come with the default hasher, like the constructors of `HashMap` do.
+impl ChainSpec {
+    pub const fn mainnet() -> Self {
+        ChainSpec {
+            genesis_validators_root: H256([
+                75, 54, 61, 185, 78, 40, 97, 32, 215, 110, 185, 5, 52, 15, 221, 78, 84, 191, 233,
+                240, 107, 243, 63, 246, 207, 90, 210, 127, 81, 27, 254, 149,
+            ]),
+            genesis_time: 1606824023,
+            genesis_fork_version: [0x00, 0x00, 0x00, 0x00],
+            altair: Fork {
+                version: [0x01, 0x00, 0x00, 0x00],
+                epoch: 74240,
+            },
+            bellatrix: Fork {
+                version: [0x02, 0x00, 0x00, 0x00],
+                epoch: 144896,
+            },
+            capella: Fork {
+                version: [0x03, 0x00, 0x00, 0x00],
+                epoch: 194048,
+            },
+            deneb: Fork {
+                version: [0x04, 0x00, 0x00, 0x00],
+                epoch: 269568,
+            },
+            electra: Fork {
+                version: [0x05, 0x00, 0x00, 0x00],
+                epoch: 364032,
+            },
+            hasher: PhantomData,
+        }
+    }
+
+    pub const fn sepolia() -> Self {
+        ChainSpec {
+            genesis_validators_root: H256([
+                216, 234, 23, 31, 60, 148, 174, 162, 30, 188, 66, 161, 237, 97, 5, 42, 207, 63,
+                146, 9, 192, 14, 78, 251, 170, 221, 172, 9, 237, 155, 128, 120,
+            ]),
+            genesis_time: 1655733600,
+            genesis_fork_version: [0x90, 0x00, 0x00, 0x69],
+            altair: Fork {
+                version: [0x90, 0x00, 0x00, 0x70],
+                epoch: 50,
+            },
+            bellatrix: Fork {
+                version: [0x90, 0x00, 0x00, 0x71],
+                epoch: 100,
+            },
+            capella: Fork {
+                version: [0x90, 0x00, 0x00, 0x72],
+                epoch: 56832,
+            },
+            deneb: Fork {
+                version: [0x90, 0x00, 0x00, 0x73],
+                epoch: 132608,
+            },
+            electra: Fork {
+                version: [0x90, 0x00, 0x00, 0x74],
+                epoch: 222464,
+            },
+            hasher: PhantomData,
+        }
+    }
+
+    pub const fn holesky() -> Self {
+        ChainSpec {
+            genesis_validators_root: H256([
+                145, 67, 170, 124, 97, 90, 127, 113, 21, 226, 182, 170, 195, 25, 192, 53, 41,
+                223, 130, 66, 174, 112, 95, 186, 157, 243, 155, 121, 197, 159, 168, 177,
+            ]),
+            genesis_time: 1695902400,
+            genesis_fork_version: [0x01, 0x01, 0x70, 0x00],
+            altair: Fork {
+                version: [0x02, 0x01, 0x70, 0x00],
+                epoch: 0,
+            },
+            bellatrix: Fork {
+                version: [0x03, 0x01, 0x70, 0x00],
+                epoch: 0,
+            },
+            capella: Fork {
+                version: [0x04, 0x01, 0x70, 0x00],
+                epoch: 256,
+            },
+            deneb: Fork {
+                version: [0x05, 0x01, 0x70, 0x00],
+                epoch: 29696,
+            },
+            electra: Fork {
+                version: [0x06, 0x01, 0x70, 0x00],
+                epoch: 115968,
+            },
+            hasher: PhantomData,
+        }
+    }
+
+    /// A local devnet with every fork active from genesis. The genesis validators root and time
+    /// differ for every launch so they have to be supplied, fork versions follow the defaults of
+    /// the common devnet tooling and can be overwritten on the returned value.
+    pub const fn devnet(genesis_validators_root: H256, genesis_time: u64) -> Self {
+        ChainSpec {
+            genesis_validators_root,
+            genesis_time,
+            genesis_fork_version: [0x10, 0x00, 0x00, 0x38],
+            altair: Fork {
+                version: [0x20, 0x00, 0x00, 0x38],
+                epoch: 0,
+            },
+            bellatrix: Fork {
+                version: [0x30, 0x00, 0x00, 0x38],
+                epoch: 0,
+            },
+            capella: Fork {
+                version: [0x40, 0x00, 0x00, 0x38],
+                epoch: 0,
+            },
+            deneb: Fork {
+                version: [0x50, 0x00, 0x00, 0x38],
+                epoch: 0,
+            },
+            electra: Fork {
+                version: [0x60, 0x00, 0x00, 0x38],
+                epoch: 0,
+            },
+            hasher: PhantomData,
+        }
+    }
+}
+
+impl<H> ChainSpec<H> {
+    /// The same network, verified with `H2` instead of `H`.
+    pub const fn with_hasher<H2: Hasher>(self) -> ChainSpec<H2> {
+        ChainSpec {
+            genesis_validators_root: self.genesis_validators_root,
+            genesis_time: self.genesis_time,
+            genesis_fork_version: self.genesis_fork_version,
+            altair: self.altair,
+            bellatrix: self.bellatrix,
+            capella: self.capella,
+            deneb: self.deneb,
+            electra: self.electra,
+            hasher: PhantomData,
+        }
+    }
+
+    pub fn fork_at_epoch(&self, epoch: u64) -> ForkName {
+        if epoch >= self.electra.epoch {
+            ForkName::Electra
+        } else if epoch >= self.deneb.epoch {
+            ForkName::Deneb
+        } else if epoch >= self.capella.epoch {
+            ForkName::Capella
+        } else if epoch >= self.bellatrix.epoch {
+            ForkName::Bellatrix
+        } else if epoch >= self.altair.epoch {
+            ForkName::Altair
+        } else {
+            ForkName::Phase0
+        }
+    }
+
+    pub fn fork_at_slot(&self, slot: u64) -> ForkName {
+        self.fork_at_epoch(compute_epoch_at_slot(slot))
+    }
+
+    pub fn fork_version(&self, fork: ForkName) -> ForkVersion {
+        match fork {
+            ForkName::Phase0 => self.genesis_fork_version,
+            ForkName::Altair => self.altair.version,
+            ForkName::Bellatrix => self.bellatrix.version,
+            ForkName::Capella => self.capella.version,
+            ForkName::Deneb => self.deneb.version,
+            ForkName::Electra => self.electra.version,
+        }
+    }
+
+    pub fn fork_version_at_epoch(&self, epoch: u64) -> ForkVersion {
+        self.fork_version(self.fork_at_epoch(epoch))
+    }
+
+    pub fn fork_version_at_slot(&self, slot: u64) -> ForkVersion {
+        self.fork_version_at_epoch(compute_epoch_at_slot(slot))
+    }
+}
+
+// Implemented by hand, deriving would require the hasher to implement them as well.
+impl<H> Clone for ChainSpec<H> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<H> Copy for ChainSpec<H> {}
+
+impl<H> PartialEq for ChainSpec<H> {
+    fn eq(&self, other: &Self) -> bool {
+        self.genesis_validators_root == other.genesis_validators_root
+            && self.genesis_time == other.genesis_time
+            && self.genesis_fork_version == other.genesis_fork_version
+            && self.altair == other.altair
+            && self.bellatrix == other.bellatrix
+            && self.capella == other.capella
+            && self.deneb == other.deneb
+            && self.electra == other.electra
+    }
+}
+
+impl<H> fmt::Debug for ChainSpec<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChainSpec")
+            .field("genesis_validators_root", &self.genesis_validators_root)
+            .field("genesis_time", &self.genesis_time)
+            .field("genesis_fork_version", &self.genesis_fork_version)
+            .field("altair", &self.altair)
+            .field("bellatrix", &self.bellatrix)
+            .field("capella", &self.capella)
+            .field("deneb", &self.deneb)
+            .field("electra", &self.electra)
+            .finish()
+    }
+}
@@ -0,0 +1,228 @@
+use core::fmt;
+
+/// The merkle branches checked while processing an update.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MerkleBranch {
+    NextSyncCommittee,
+    Finality,
+    CurrentSyncCommittee,
+    Execution,
+    BlockRoots,
+    HistoricalSummary,
+}
+
+/// Failures coming out of the BLS signature verification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlsError {
+    // A committee pubkey could not be decompressed into a curve point.
+    InvalidPublicKeyPoint,
+    // A committee pubkey was malformed for any other reason.
+    InvalidPublicKey,
+    // The aggregate signature bytes could not be decoded.
+    InvalidSignature,
+    // The participant pubkeys could not be aggregated.
+    InvalidAggregatePublicKeys,
+    // Everything decoded but the signature does not verify.
+    VerificationFailed,
+}
+
+/// Everything that can go wrong while verifying a light client update.
+///
+/// Every variant maps to a stable numeric code (see [`LightClientError::code`]) so that callers
+/// outside of Rust can branch on the failure kind without matching on strings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LightClientError {
+    // The SSZ input named by `field` could not be decoded.
+    Decode { field: &'static str },
+    // The value named by `field` could not be SSZ encoded.
+    Encode { field: &'static str },
+    // A fixed size value was built from a slice of the wrong length.
+    InvalidLength { expected: usize, actual: usize },
+    // Merkleization of an SSZ container failed.
+    HashTreeRoot,
+    // The named merkle branch does not lead to the expected root.
+    InvalidMerkleBranch(MerkleBranch),
+    // Fewer sync committee members signed than the threshold requires.
+    InsufficientParticipation { participants: u64, required: u64 },
+    Bls(BlsError),
+    // The update's slots are not ordered as `current >= signature > attested >= finalized`.
+    InvalidSlotOrder,
+    // The update was signed in a sync committee period the store can't verify.
+    InvalidSyncCommitteePeriod { store_period: u64, update_period: u64 },
+    // The update neither advances the store nor provides a missing next sync committee.
+    IrrelevantUpdate,
+    // A finalized header was supplied without a finality branch.
+    UnexpectedFinalizedHeader,
+    // A next sync committee was supplied without a sync committee branch.
+    UnexpectedNextSyncCommittee,
+    // The next sync committee differs from the one already known to the store.
+    NextSyncCommitteeMismatch,
+    // The historical header can't be proven from the finalized state with the given proof.
+    InvalidAncestorSlot { finalized_slot: u64, ancestor_slot: u64 },
+    // The update was signed longer ago than the verification policy allows.
+    StaleUpdate { signature_slot: u64, current_slot: u64 },
+    // The bootstrap header does not hash to the trusted block root.
+    UntrustedBootstrapHeader,
+    // The execution payload of a light client header doesn't fit the fork of its slot.
+    InvalidLightClientHeader,
+    // A Merkle-Patricia trie proof doesn't lead from the root to the proven key.
+    InvalidTrieProof,
+}
+
+/// The first update of a chain of period updates that failed to verify, or of a list of candidate
+/// updates that failed to decode.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpdateChainError {
+    // Position of the failing update in the chain, `None` if the checkpoint itself is unusable.
+    pub index: Option<usize>,
+    pub error: LightClientError,
+}
+
+impl LightClientError {
+    /// Stable numeric identifier of the error kind. These values must never be reused or
+    /// renumbered once released, append new ones instead.
+    pub fn code(&self) -> u32 {
+        match self {
+            LightClientError::Decode { .. } => 1,
+            LightClientError::InvalidLength { .. } => 2,
+            LightClientError::HashTreeRoot => 3,
+            LightClientError::Encode { .. } => 4,
+            LightClientError::InvalidMerkleBranch(MerkleBranch::NextSyncCommittee) => 10,
+            LightClientError::InvalidMerkleBranch(MerkleBranch::Finality) => 11,
+            LightClientError::InvalidMerkleBranch(MerkleBranch::CurrentSyncCommittee) => 12,
+            LightClientError::InvalidMerkleBranch(MerkleBranch::Execution) => 13,
+            LightClientError::InvalidMerkleBranch(MerkleBranch::BlockRoots) => 14,
+            LightClientError::InvalidMerkleBranch(MerkleBranch::HistoricalSummary) => 15,
+            LightClientError::InsufficientParticipation { .. } => 20,
+            LightClientError::Bls(BlsError::InvalidPublicKeyPoint) => 30,
+            LightClientError::Bls(BlsError::InvalidPublicKey) => 31,
+            LightClientError::Bls(BlsError::InvalidSignature) => 32,
+            LightClientError::Bls(BlsError::InvalidAggregatePublicKeys) => 33,
+            LightClientError::Bls(BlsError::VerificationFailed) => 34,
+            LightClientError::InvalidSlotOrder => 40,
+            LightClientError::InvalidSyncCommitteePeriod { .. } => 41,
+            LightClientError::IrrelevantUpdate => 42,
+            LightClientError::UnexpectedFinalizedHeader => 43,
+            LightClientError::UnexpectedNextSyncCommittee => 44,
+            LightClientError::NextSyncCommitteeMismatch => 45,
+            LightClientError::InvalidAncestorSlot { .. } => 46,
+            LightClientError::StaleUpdate { .. } => 47,
+            LightClientError::UntrustedBootstrapHeader => 50,
+            LightClientError::InvalidLightClientHeader => 60,
+            LightClientError::InvalidTrieProof => 70,
+        }
+    }
+}
+
+impl From<BlsError> for LightClientError {
+    fn from(e: BlsError) -> Self {
+        LightClientError::Bls(e)
+    }
+}
+
+impl fmt::Display for UpdateChainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.index {
+            Some(index) => write!(f, "update {}: {}", index, self.error),
+            None => write!(f, "checkpoint: {}", self.error),
+        }
+    }
+}
+
+impl fmt::Display for MerkleBranch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MerkleBranch::NextSyncCommittee => write!(f, "next sync committee"),
+            MerkleBranch::Finality => write!(f, "finality"),
+            MerkleBranch::CurrentSyncCommittee => write!(f, "current sync committee"),
+            MerkleBranch::Execution => write!(f, "execution payload"),
+            MerkleBranch::BlockRoots => write!(f, "block roots"),
+            MerkleBranch::HistoricalSummary => write!(f, "historical summary"),
+        }
+    }
+}
+
+impl fmt::Display for BlsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlsError::InvalidPublicKeyPoint => write!(f, "invalid public key point"),
+            BlsError::InvalidPublicKey => write!(f, "invalid public key"),
+            BlsError::InvalidSignature => write!(f, "invalid signature"),
+            BlsError::InvalidAggregatePublicKeys => write!(f, "invalid aggregate public keys"),
+            BlsError::VerificationFailed => write!(f, "signature verification failed"),
+        }
+    }
+}
+
+impl fmt::Display for LightClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LightClientError::Decode { field } => write!(f, "failed to decode {}", field),
+            LightClientError::Encode { field } => write!(f, "failed to encode {}", field),
+            LightClientError::InvalidLength { expected, actual } => {
+                write!(f, "invalid length: expected {} bytes, got {}", expected, actual)
+            }
+            LightClientError::HashTreeRoot => write!(f, "hash tree root failed"),
+            LightClientError::InvalidMerkleBranch(branch) => {
+                write!(f, "{} merkle branch is invalid", branch)
+            }
+            LightClientError::InsufficientParticipation {
+                participants,
+                required,
+            } => write!(
+                f,
+                "sync committee participation too low: {} of {} required",
+                participants, required
+            ),
+            LightClientError::Bls(e) => write!(f, "bls: {}", e),
+            LightClientError::InvalidSlotOrder => write!(f, "update slots are out of order"),
+            LightClientError::InvalidSyncCommitteePeriod {
+                store_period,
+                update_period,
+            } => write!(
+                f,
+                "update period {} can't be verified from store period {}",
+                update_period, store_period
+            ),
+            LightClientError::IrrelevantUpdate => write!(f, "update is not relevant to the store"),
+            LightClientError::UnexpectedFinalizedHeader => {
+                write!(f, "finalized header present without a finality branch")
+            }
+            LightClientError::UnexpectedNextSyncCommittee => {
+                write!(f, "next sync committee present without a sync committee branch")
+            }
+            LightClientError::NextSyncCommitteeMismatch => {
+                write!(f, "next sync committee does not match the known one")
+            }
+            LightClientError::InvalidAncestorSlot {
+                finalized_slot,
+                ancestor_slot,
+            } => write!(
+                f,
+                "slot {} can't be proven as an ancestor of slot {} with this proof",
+                ancestor_slot, finalized_slot
+            ),
+            LightClientError::StaleUpdate {
+                signature_slot,
+                current_slot,
+            } => write!(
+                f,
+                "update signed at slot {} is too old at slot {}",
+                signature_slot, current_slot
+            ),
+            LightClientError::UntrustedBootstrapHeader => {
+                write!(f, "bootstrap header does not match the trusted block root")
+            }
+            LightClientError::InvalidLightClientHeader => {
+                write!(f, "execution payload does not match the fork of the header")
+            }
+            LightClientError::InvalidTrieProof => write!(f, "trie proof does not match the root"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LightClientError {}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UpdateChainError {}
@@ -1,46 +1,42 @@
 use super::types::*;
 use super::utils::*;
-use alloc::vec::Vec;
-use alloc::string::String;
+use crate::bls::{BlsVerifier, PreparedSyncCommittee};
+use crate::chain_spec::ChainSpec;
+use crate::error::{LightClientError, MerkleBranch};
+use crate::policy::VerificationPolicy;
+use crate::hasher::Hasher;
 
 
-pub fn process_finalized_header(update: FinalizedHeaderUpdate, sync_committee:SyncCommittee, validators_root: H256) -> Result<BeaconHeader, String> {
-    let sync_committee_bits = get_sync_committee_bits(update.sync_aggregate.sync_committee_bits.clone())?;
-    sync_committee_participation_is_supermajority(sync_committee_bits.clone())?;
+pub fn process_finalized_header<V: BlsVerifier, H: Hasher>(update: FinalizedHeaderUpdate, sync_committee: &PreparedSyncCommittee<V>, current_slot: u64, policy: &VerificationPolicy, spec: &ChainSpec<H>) -> Result<LightClientHeader, LightClientError> {
+    let sync_committee_bits = get_sync_committee_bits(&update.sync_aggregate.sync_committee_bits)?;
+    policy.verify_finality_participation(get_sync_committee_sum(&sync_committee_bits))?;
+    policy.verify_update_age(update.signature_slot, current_slot)?;
+    verify_light_client_header(&update.attested_header, spec)?;
+    verify_light_client_header(&update.finalized_header, spec)?;
 
-    let block_root = H256(hash_tree_root_beacon_header(update.finalized_header.clone())?);
+    let block_root = update.finalized_block_root();
+    let gindex = finalized_root_gindex(spec, update.attested_header.beacon.slot);
         
-    verify_header(
+    verify_header::<H>(
         block_root,
         update.finality_branch,
-        update.attested_header.state_root,
-        FINALIZED_ROOT_DEPTH,
-        FINALIZED_ROOT_INDEX,
+        update.attested_header.beacon.state_root,
+        gindex,
+        MerkleBranch::Finality,
     )?;
 
     // let current_period = compute_current_sync_period(update.attested_header.slot);
     // let sync_committee = Self::get_sync_committee_for_period(current_period)?;
-    let fork_version: [u8;4] = [2,0,0,0];
     verify_signed_header(
         sync_committee_bits,
         update.sync_aggregate.sync_committee_signature,
-        sync_committee.pubkeys,
-        fork_version,
-        update.attested_header,
-        validators_root,
+        sync_committee,
+        update.signature_slot,
+        update.attested_header.beacon,
+        spec,
     )?;
 
     // Self::store_finalized_header(block_root, update.finalized_header);
 
     Ok(update.finalized_header)
 }
-
-fn sync_committee_participation_is_supermajority(sync_committee_bits: Vec<u8>) -> Result<(), String> {
-    let sync_committee_sum = get_sync_committee_sum(sync_committee_bits.clone());
-    
-    if sync_committee_sum * 3 >= sync_committee_bits.clone().len() as u64 * 2 {
-        Ok(())
-    } else {
-        Err("Sync committee participation is not supermajority".into())
-    }
-}
\ No newline at end of file
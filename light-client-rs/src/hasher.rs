@@ -0,0 +1,127 @@
+//! SHA-256 used by bacon's own merkleization and merkle branch verification.
+//!
+//! Hashing dominates the instruction count of the MIPS verifier, so the implementation is a type
+//! parameter that a host accelerated one can replace. A [`ChainSpec`](crate::ChainSpec) carries
+//! the hasher its updates are verified with, [`DefaultHasher`] unless swapped with
+//! [`ChainSpec::with_hasher`](crate::ChainSpec::with_hasher). That is [`Sha2Hasher`] with the
+//! `sha2` feature (default) and the dependency free [`SoftwareHasher`] without it.
+
+pub trait Hasher {
+    fn hash(data: &[u8]) -> [u8; 32];
+
+    /// Hash of the concatenation of two nodes, the only input size merkleization needs.
+    fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        let mut data = [0u8; 64];
+        data[0..32].copy_from_slice(left);
+        data[32..64].copy_from_slice(right);
+        Self::hash(&data)
+    }
+}
+
+#[cfg(feature = "sha2")]
+#[derive(Clone, Copy, Debug)]
+pub struct Sha2Hasher;
+
+#[cfg(feature = "sha2")]
+impl Hasher for Sha2Hasher {
+    fn hash(data: &[u8]) -> [u8; 32] {
+        use sha2::{Digest, Sha256};
+        let mut hasher = Sha256::new();
+        hasher.update(data);
+        let mut digest = [0u8; 32];
+        digest.copy_from_slice(&hasher.finalize());
+        digest
+    }
+}
+
+/// Portable SHA-256 without any dependency, for targets where the `sha2` crate isn't wanted.
+#[derive(Clone, Copy, Debug)]
+pub struct SoftwareHasher;
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+const H0: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+impl Hasher for SoftwareHasher {
+    fn hash(data: &[u8]) -> [u8; 32] {
+        let mut state = H0;
+        let mut blocks = data.chunks_exact(64);
+        for block in &mut blocks {
+            compress(&mut state, block);
+        }
+
+        // Pad with a single one bit, zeros and the message length in bits.
+        let remainder = blocks.remainder();
+        let mut tail = [0u8; 128];
+        tail[..remainder.len()].copy_from_slice(remainder);
+        tail[remainder.len()] = 0x80;
+        let tail_len = if remainder.len() < 56 { 64 } else { 128 };
+        tail[tail_len - 8..tail_len].copy_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+        for block in tail[..tail_len].chunks_exact(64) {
+            compress(&mut state, block);
+        }
+
+        let mut digest = [0u8; 32];
+        for (bytes, word) in digest.chunks_exact_mut(4).zip(state.iter()) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+        digest
+    }
+}
+
+fn compress(state: &mut [u32; 8], block: &[u8]) {
+    let mut w = [0u32; 64];
+    for (word, bytes) in w.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+    for t in 16..64 {
+        let s0 = w[t - 15].rotate_right(7) ^ w[t - 15].rotate_right(18) ^ (w[t - 15] >> 3);
+        let s1 = w[t - 2].rotate_right(17) ^ w[t - 2].rotate_right(19) ^ (w[t - 2] >> 10);
+        w[t] = w[t - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[t - 7])
+            .wrapping_add(s1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for t in 0..64 {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let t1 = h
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(K[t])
+            .wrapping_add(w[t]);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let t2 = s0.wrapping_add(maj);
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+    }
+
+    for (word, value) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *word = word.wrapping_add(value);
+    }
+}
+
+#[cfg(feature = "sha2")]
+pub type DefaultHasher = Sha2Hasher;
+#[cfg(not(feature = "sha2"))]
+pub type DefaultHasher = SoftwareHasher;
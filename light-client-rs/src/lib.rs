@@ -2,23 +2,68 @@
 // #![cfg_attr(not(feature = "std"), no_std)]
 #![no_std]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
+pub mod ancestry;
+#[cfg(feature = "serde")]
+pub mod beacon_api;
+pub mod bls;
+pub mod bootstrap;
+pub mod chain_spec;
+pub mod error;
 pub mod finalized_header;
+pub mod hasher;
+pub mod merkle;
+pub mod mpt;
+pub mod optimistic_update;
+pub mod policy;
+pub mod preset;
+pub mod receipt;
+pub mod store;
 pub mod types;
 pub mod update_sync_committee;
 pub mod utils;
+pub mod view;
 
+use alloc::vec::Vec;
+
+pub use ancestry::{verify_ancestry, AncestryProof};
+#[cfg(feature = "serde")]
+pub use beacon_api::Versioned;
+pub use bls::{BlsVerifier, DefaultBlsVerifier, PreparedSyncCommittee};
+#[cfg(feature = "blst")]
+pub use bls::BlstVerifier;
+#[cfg(feature = "milagro")]
+pub use bls::MilagroVerifier;
+pub use bootstrap::initialize_from_bootstrap;
+pub use chain_spec::{ChainSpec, Fork, ForkName};
+pub use error::{BlsError, LightClientError, MerkleBranch, UpdateChainError};
 pub use finalized_header::process_finalized_header;
+#[cfg(feature = "sha2")]
+pub use hasher::Sha2Hasher;
+pub use hasher::{DefaultHasher, Hasher, SoftwareHasher};
+pub use mpt::{verify_account_proof, verify_account_proof_at_header, verify_storage_proof, Account};
+pub use optimistic_update::process_optimistic_update;
+pub use policy::VerificationPolicy;
+pub use receipt::{
+    verify_receipt_proof, verify_receipt_proof_at_header, Log, Receipt, TransactionType,
+};
+pub use store::{
+    apply_light_client_update, is_better_update, process_light_client_store_force_update,
+    process_light_client_update, LightClientStore,
+};
+#[cfg(feature = "milagro")]
 pub use milagro_bls::{AggregatePublicKey, AggregateSignature, AmclError, Signature};
-use ssz_rs::deserialize;
 pub use ssz_rs::{
     prelude::Vector, Bitvector, Deserialize, SimpleSerialize as SimpleSerializeTrait, Sized,
 };
 pub use types::*;
-pub use update_sync_committee::process_sync_committee_period_update;
-
-use crate::alloc::string::ToString;
-use alloc::string::String;
+pub use update_sync_committee::{
+    process_sync_committee_period_update, process_sync_committee_period_update_view,
+    process_sync_committee_update_chain, process_sync_committee_update_chain_view,
+};
+pub use view::{SyncCommitteePeriodUpdateView, SyncCommitteeView};
 
 macro_rules! tryprintln {
     ($body:expr) => {
@@ -26,37 +71,135 @@ macro_rules! tryprintln {
     };
 }
 
-pub fn ssz_process_sync_committee_period_update(
+/// Returns the next sync committee, the finalized header and its block root.
+pub fn ssz_process_sync_committee_period_update<H: Hasher>(
     prev_update: &[u8],
     update: &[u8],
-    validators_root: H256,
-) -> Result<(SyncCommittee, BeaconHeader), String> {
-    // deserialize from bytes into structured types for the sync committee
-    let prev_update = SyncCommitteePeriodUpdate::try_from(prev_update)?;
-    let update = SyncCommitteePeriodUpdate::try_from(update)?;
+    policy: &VerificationPolicy,
+    spec: &ChainSpec<H>,
+) -> Result<(SyncCommittee, LightClientHeader, H256), LightClientError> {
+    // borrow the fields straight from the bytes instead of decoding the sync committees
+    let prev_update = SyncCommitteePeriodUpdateView::new(prev_update, spec)?;
+    let update = SyncCommitteePeriodUpdateView::new(update, spec)?;
 
     // Process the update between the prev and current updates
     // If it validates successfully returns Ok()
-    // Otherwise returns the error string
-    process_sync_committee_period_update(prev_update, update, validators_root)
+    // Otherwise returns the error
+    let (sync_committee, finalized_header) =
+        process_sync_committee_period_update_view::<DefaultBlsVerifier, H>(
+            &prev_update,
+            &update,
+            policy,
+            spec,
+        )?;
+    let block_root = finalized_header.beacon.hash_tree_root_with::<H>();
+    Ok((sync_committee.into(), finalized_header, block_root))
+}
+
+/// Returns the next sync committee, the finalized header and its block root of the last update.
+pub fn ssz_process_sync_committee_update_chain<H: Hasher>(
+    checkpoint: &[u8],
+    updates: &[&[u8]],
+    policy: &VerificationPolicy,
+    spec: &ChainSpec<H>,
+) -> Result<(SyncCommittee, LightClientHeader, H256), UpdateChainError> {
+    let checkpoint = SyncCommitteePeriodUpdateView::new(checkpoint, spec)
+        .map_err(|error| UpdateChainError { index: None, error })?;
+    let updates = updates
+        .iter()
+        .enumerate()
+        .map(|(index, update)| {
+            SyncCommitteePeriodUpdateView::new(update, spec).map_err(|error| UpdateChainError {
+                index: Some(index),
+                error,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (sync_committee, finalized_header) =
+        process_sync_committee_update_chain_view::<DefaultBlsVerifier, H>(
+            checkpoint, updates, policy, spec,
+        )?;
+    let block_root = finalized_header.beacon.hash_tree_root_with::<H>();
+    Ok((sync_committee.into(), finalized_header, block_root))
+}
+
+/// Index of the best of the encoded period updates by [`is_better_update`], the first one of
+/// equally good candidates and `None` if there are none. The ranking takes the sync committee
+/// bits at face value, candidates from untrusted sources have to be verified as well.
+pub fn select_best_update<H: Hasher>(
+    candidates: &[&[u8]],
+    spec: &ChainSpec<H>,
+) -> Result<Option<usize>, UpdateChainError> {
+    let mut best: Option<(usize, SyncCommitteePeriodUpdate)> = None;
+    for (index, candidate) in candidates.iter().enumerate() {
+        let candidate = SyncCommitteePeriodUpdate::decode(candidate, spec).map_err(|error| {
+            UpdateChainError {
+                index: Some(index),
+                error,
+            }
+        })?;
+        match &best {
+            Some((_, best_update)) if !is_better_update(&candidate, best_update) => {}
+            _ => best = Some((index, candidate)),
+        }
+    }
+    Ok(best.map(|(index, _)| index))
 }
 
-pub fn ssz_process_finalized_header(
+/// Returns the finalized header and its block root.
+pub fn ssz_process_finalized_header<H: Hasher>(
     update: &[u8],
     sync_committee: &[u8],
-    validators_root: H256,
-) -> Result<BeaconHeader, String> {
+    current_slot: u64,
+    policy: &VerificationPolicy,
+    spec: &ChainSpec<H>,
+) -> Result<(LightClientHeader, H256), LightClientError> {
     tryprintln!("entry point");
-    let update: SSZFinalizedHeaderUpdate =
-        deserialize(&update).map_err(|_e| "Failed to decode previous update".to_string())?;
+    let update = FinalizedHeaderUpdate::decode(update, spec)?;
     tryprintln!("decode 1");
-    let sync_committee: SSZSyncCommittee =
-        SSZSyncCommittee::deserialize(&sync_committee).map_err(|_| "Failed to decode update")?;
+    let sync_committee: SSZSyncCommittee = SSZSyncCommittee::deserialize(&sync_committee)
+        .map_err(|_| LightClientError::Decode {
+            field: "sync_committee",
+        })?;
     tryprintln!("decode 2");
 
-    let update = FinalizedHeaderUpdate::from(update);
-    let sync_committee = SyncCommittee::from(sync_committee);
+    let sync_committee: PreparedSyncCommittee = PreparedSyncCommittee::lazy(sync_committee.into());
+
+    let finalized_header =
+        process_finalized_header(update, &sync_committee, current_slot, policy, spec)?;
+    let block_root = finalized_header.beacon.hash_tree_root_with::<H>();
+    Ok((finalized_header, block_root))
+}
+
+/// Returns the attested header and its block root.
+pub fn ssz_process_optimistic_update<H: Hasher>(
+    update: &[u8],
+    sync_committee: &[u8],
+    current_slot: u64,
+    policy: &VerificationPolicy,
+    spec: &ChainSpec<H>,
+) -> Result<(LightClientHeader, H256), LightClientError> {
+    let update = LightClientOptimisticUpdate::decode(update, spec)?;
+    let sync_committee: SSZSyncCommittee = SSZSyncCommittee::deserialize(&sync_committee)
+        .map_err(|_| LightClientError::Decode {
+            field: "sync_committee",
+        })?;
+
+    let sync_committee: PreparedSyncCommittee = PreparedSyncCommittee::lazy(sync_committee.into());
+
+    let attested_header =
+        process_optimistic_update(update, &sync_committee, current_slot, policy, spec)?;
+    let block_root = attested_header.beacon.hash_tree_root_with::<H>();
+    Ok((attested_header, block_root))
+}
+
+pub fn ssz_initialize_from_bootstrap<H: Hasher>(
+    trusted_block_root: H256,
+    bootstrap: &[u8],
+    spec: &ChainSpec<H>,
+) -> Result<LightClientStore, LightClientError> {
+    let bootstrap = LightClientBootstrap::decode(bootstrap, spec)?;
 
-    process_finalized_header(update, sync_committee, validators_root)
-        .map_err(|_e| "failed sync comitte update period: {}".to_string())
+    initialize_from_bootstrap(trusted_block_root, bootstrap, spec)
 }
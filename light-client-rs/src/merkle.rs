@@ -0,0 +1,194 @@
+//! Merkle proofs addressed by generalized index.
+//!
+//! A generalized index (gindex) numbers the nodes of a binary merkle tree breadth first, the root
+//! is 1 and the children of `i` are `2i` and `2i + 1`. Its bit length is the depth of the node and
+//! the bits below the leading one are the path from the root. The functions here follow the
+//! `ssz/merkle-proofs.md` document of the consensus specs.
+
+use super::types::H256;
+use crate::hasher::Hasher;
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+pub type GeneralizedIndex = u64;
+
+/// Depth of the node at `index`, zero for the root. Zero isn't a gindex and is given the depth of
+/// the root instead of underflowing.
+pub fn get_generalized_index_length(index: GeneralizedIndex) -> u32 {
+    63u32.saturating_sub(index.leading_zeros())
+}
+
+/// Position of the node at `index` among the nodes of its depth.
+pub fn get_subtree_index(index: GeneralizedIndex) -> u64 {
+    index % (1 << get_generalized_index_length(index))
+}
+
+/// Combines the gindices of a path through nested containers, each one relative to the root of
+/// the previous, into the gindex from the outermost root.
+pub fn concat_generalized_indices(indices: &[GeneralizedIndex]) -> GeneralizedIndex {
+    indices.iter().fold(1, |outer, index| {
+        let depth = get_generalized_index_length(*index);
+        outer << depth | get_subtree_index(*index)
+    })
+}
+
+/// Root of the tree with `leaf` at `index` and the sibling `branch`, ordered from the leaf up.
+/// `None` if `index` is zero or the branch length doesn't match its depth.
+pub fn compute_merkle_root<H: Hasher>(
+    leaf: H256,
+    branch: impl IntoIterator<Item = H256>,
+    index: GeneralizedIndex,
+) -> Option<H256> {
+    if index == 0 {
+        return None;
+    }
+    let depth = get_generalized_index_length(index);
+    let mut value = leaf.0;
+    let mut length = 0;
+    for (i, node) in branch.into_iter().enumerate() {
+        if i as u32 >= depth {
+            return None;
+        }
+        value = if (index >> i) & 1 == 0 {
+            H::hash_pair(&value, &node.0)
+        } else {
+            H::hash_pair(&node.0, &value)
+        };
+        length += 1;
+    }
+    if length != depth {
+        return None;
+    }
+    Some(H256(value))
+}
+
+pub fn is_valid_merkle_branch<H: Hasher>(
+    leaf: H256,
+    branch: impl IntoIterator<Item = H256>,
+    index: GeneralizedIndex,
+    root: H256,
+) -> bool {
+    compute_merkle_root::<H>(leaf, branch, index) == Some(root)
+}
+
+fn get_branch_indices(index: GeneralizedIndex) -> Vec<GeneralizedIndex> {
+    let mut branch = vec![];
+    let mut index = index;
+    while index > 1 {
+        branch.push(index ^ 1);
+        index /= 2;
+    }
+    branch
+}
+
+fn get_path_indices(index: GeneralizedIndex) -> Vec<GeneralizedIndex> {
+    let mut path = vec![];
+    let mut index = index;
+    while index > 1 {
+        path.push(index);
+        index /= 2;
+    }
+    path
+}
+
+/// The nodes a multiproof for `indices` has to provide, in the order the proof lists them.
+pub fn get_helper_indices(indices: &[GeneralizedIndex]) -> Vec<GeneralizedIndex> {
+    let mut helpers = Vec::new();
+    let mut paths = Vec::new();
+    for index in indices {
+        helpers.extend(get_branch_indices(*index));
+        paths.extend(get_path_indices(*index));
+    }
+    helpers.retain(|index| !paths.contains(index));
+    helpers.sort_unstable_by(|a, b| b.cmp(a));
+    helpers.dedup();
+    helpers
+}
+
+/// Root of the tree with `leaves` at `indices`, completed by the `proof` nodes at
+/// [`get_helper_indices`]. `None` if an index is zero or the proof doesn't have exactly the
+/// required nodes.
+pub fn calculate_multi_merkle_root<H: Hasher>(
+    leaves: &[H256],
+    proof: &[H256],
+    indices: &[GeneralizedIndex],
+) -> Option<H256> {
+    if indices.contains(&0) {
+        return None;
+    }
+    let helper_indices = get_helper_indices(indices);
+    if leaves.len() != indices.len() || proof.len() != helper_indices.len() {
+        return None;
+    }
+    let mut objects = BTreeMap::new();
+    for (index, node) in indices.iter().zip(leaves).chain(helper_indices.iter().zip(proof)) {
+        if objects.insert(*index, node.0).is_some() {
+            return None;
+        }
+    }
+
+    // Hash the deepest known pairs first, every new parent is a candidate for the next round.
+    let mut keys: Vec<GeneralizedIndex> = objects.keys().rev().copied().collect();
+    let mut position = 0;
+    while position < keys.len() {
+        let key = keys[position];
+        if key > 1 && !objects.contains_key(&(key / 2)) {
+            if let (Some(left), Some(right)) =
+                (objects.get(&(key & !1)), objects.get(&(key | 1)))
+            {
+                let parent = H::hash_pair(left, right);
+                objects.insert(key / 2, parent);
+                keys.push(key / 2);
+            }
+        }
+        position += 1;
+    }
+    objects.get(&1).copied().map(H256)
+}
+
+pub fn verify_merkle_multiproof<H: Hasher>(
+    leaves: &[H256],
+    proof: &[H256],
+    indices: &[GeneralizedIndex],
+    root: H256,
+) -> bool {
+    calculate_multi_merkle_root::<H>(leaves, proof, indices) == Some(root)
+}
+
+/// Every node of the tree over `leaves`, padded with zero leaves to a power of two, by gindex.
+/// Meant for building proofs in tests and tooling, it holds the whole tree in memory.
+pub fn merkle_tree<H: Hasher>(leaves: &[H256]) -> Vec<H256> {
+    let width = leaves.len().max(1).next_power_of_two();
+    let mut tree = vec![H256::default(); 2 * width];
+    tree[width..width + leaves.len()].copy_from_slice(leaves);
+    for index in (1..width).rev() {
+        tree[index] = H256(H::hash_pair(&tree[2 * index].0, &tree[2 * index + 1].0));
+    }
+    tree
+}
+
+/// The branch proving the node at `index` of the tree over `leaves`, `None` if the tree has no
+/// such node.
+pub fn build_proof<H: Hasher>(leaves: &[H256], index: GeneralizedIndex) -> Option<Vec<H256>> {
+    if index == 0 {
+        return None;
+    }
+    let tree = merkle_tree::<H>(leaves);
+    get_branch_indices(index)
+        .into_iter()
+        .map(|index| tree.get(index as usize).copied())
+        .collect()
+}
+
+/// The multiproof for the nodes at `indices` of the tree over `leaves`.
+pub fn build_multiproof<H: Hasher>(
+    leaves: &[H256],
+    indices: &[GeneralizedIndex],
+) -> Option<Vec<H256>> {
+    let tree = merkle_tree::<H>(leaves);
+    get_helper_indices(indices)
+        .into_iter()
+        .map(|index| tree.get(index as usize).copied())
+        .collect()
+}
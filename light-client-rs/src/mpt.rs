@@ -0,0 +1,221 @@
+//! Merkle-Patricia trie proofs of execution layer state.
+//!
+//! Checks the account and storage proofs returned by `eth_getProof` against the state root of an
+//! execution payload header. Proof nodes are the RLP encoded trie nodes on the path from the root
+//! to the key, in that order, nodes shorter than 32 bytes are embedded in their parent and not
+//! listed separately. A proof either leads to the value stored under the key or shows that there
+//! is none.
+
+use super::types::*;
+use crate::chain_spec::ChainSpec;
+use crate::error::LightClientError;
+use alloc::vec::Vec;
+use rlp::Rlp;
+use tiny_keccak::{Hasher as _, Keccak};
+use crate::hasher::Hasher;
+
+// Root of the trie without any entries, keccak256(rlp("")).
+pub const EMPTY_TRIE_ROOT: H256 = H256([
+    0x56, 0xe8, 0x1f, 0x17, 0x1b, 0xcc, 0x55, 0xa6, 0xff, 0x83, 0x45, 0xe6, 0x92, 0xc0, 0xf8, 0x6e,
+    0x5b, 0x48, 0xe0, 0x1b, 0x99, 0x6c, 0xad, 0xc0, 0x01, 0x62, 0x2f, 0xb5, 0xe3, 0x63, 0xb4, 0x21,
+]);
+
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    hasher.update(data);
+    let mut digest = [0u8; 32];
+    hasher.finalize(&mut digest);
+    digest
+}
+
+/// An account of the state trie.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Account {
+    pub nonce: u64,
+    // uint256, big endian as in its RLP encoding
+    pub balance: [u8; 32],
+    pub storage_root: H256,
+    pub code_hash: H256,
+}
+
+impl Account {
+    fn decode(bytes: &[u8]) -> Result<Self, LightClientError> {
+        let decode_error = |_| LightClientError::Decode { field: "account" };
+        let rlp = Rlp::new(bytes);
+        if rlp.item_count().map_err(decode_error)? != 4 {
+            return Err(LightClientError::Decode { field: "account" });
+        }
+        Ok(Account {
+            nonce: rlp.val_at(0).map_err(decode_error)?,
+            balance: decode_uint256(&rlp.at(1).map_err(decode_error)?)?,
+            storage_root: decode_hash(&rlp.at(2).map_err(decode_error)?)?,
+            code_hash: decode_hash(&rlp.at(3).map_err(decode_error)?)?,
+        })
+    }
+}
+
+/// Verifies the proof of the account at `address` in the state trie with root `state_root`,
+/// `None` if the proof shows that the account doesn't exist.
+pub fn verify_account_proof<P: AsRef<[u8]>>(
+    state_root: H256,
+    address: &[u8; 20],
+    proof: &[P],
+) -> Result<Option<Account>, LightClientError> {
+    verify_proof(state_root, &keccak256(address), proof)?
+        .map(|value| Account::decode(&value))
+        .transpose()
+}
+
+/// Verifies the proof of the storage `slot` in the storage trie with root `storage_root` and
+/// returns its big endian value, slots that were never written read as zero.
+pub fn verify_storage_proof<P: AsRef<[u8]>>(
+    storage_root: H256,
+    slot: &H256,
+    proof: &[P],
+) -> Result<[u8; 32], LightClientError> {
+    match verify_proof(storage_root, &keccak256(&slot.0), proof)? {
+        Some(value) => decode_uint256(&Rlp::new(&value)),
+        None => Ok([0u8; 32]),
+    }
+}
+
+/// Verifies the proof of the account at `address` against the execution state root of `header`,
+/// after checking its execution payload header against the beacon block body.
+pub fn verify_account_proof_at_header<P: AsRef<[u8]>, H: Hasher>(
+    header: &LightClientHeader,
+    address: &[u8; 20],
+    proof: &[P],
+    spec: &ChainSpec<H>,
+) -> Result<Option<Account>, LightClientError> {
+    let execution = header.verified_execution(spec)?;
+    verify_account_proof(execution.state_root, address, proof)
+}
+
+/// Walks the trie with root `root` along `key` through the `proof` nodes and returns the value
+/// stored under it, `None` if the proof ends in a node showing the key is absent.
+pub fn verify_proof<P: AsRef<[u8]>>(
+    root: H256,
+    key: &[u8],
+    proof: &[P],
+) -> Result<Option<Vec<u8>>, LightClientError> {
+    if root == EMPTY_TRIE_ROOT && proof.is_empty() {
+        return Ok(None);
+    }
+    let key: Vec<u8> = key.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]).collect();
+    let mut key = &key[..];
+    let mut nodes = proof.iter().map(AsRef::as_ref);
+
+    let mut reference = NodeReference::Hash(root);
+    let value = loop {
+        let node = match reference {
+            NodeReference::Hash(hash) => {
+                let node = nodes.next().ok_or(LightClientError::InvalidTrieProof)?;
+                if keccak256(node) != hash.0 {
+                    return Err(LightClientError::InvalidTrieProof);
+                }
+                Rlp::new(node)
+            }
+            NodeReference::Inline(node) => node,
+            NodeReference::Empty => break None,
+        };
+
+        match node.item_count().map_err(decode_error)? {
+            17 => {
+                if let Some((nibble, rest)) = key.split_first() {
+                    let child = node.at(*nibble as usize).map_err(decode_error)?;
+                    reference = NodeReference::new(child)?;
+                    key = rest;
+                } else {
+                    let value = node.at(16).map_err(decode_error)?.data().map_err(decode_error)?;
+                    break (!value.is_empty()).then(|| value.to_vec());
+                }
+            }
+            2 => {
+                let path = node.at(0).map_err(decode_error)?.data().map_err(decode_error)?;
+                let (is_leaf, path) = decode_path(path)?;
+                let matches = key.starts_with(&path);
+                if is_leaf {
+                    let value = node.at(1).map_err(decode_error)?.data().map_err(decode_error)?;
+                    break (matches && key.len() == path.len()).then(|| value.to_vec());
+                }
+                if !matches {
+                    break None;
+                }
+                reference = NodeReference::new(node.at(1).map_err(decode_error)?)?;
+                key = &key[path.len()..];
+            }
+            _ => return Err(LightClientError::Decode { field: "trie node" }),
+        }
+    };
+
+    // Every node of the proof has to be on the path, a longer proof proves something else.
+    if nodes.next().is_some() {
+        return Err(LightClientError::InvalidTrieProof);
+    }
+    Ok(value)
+}
+
+// How a trie node refers to a child: by hash, embedded if its encoding is shorter than a hash, or
+// not at all.
+enum NodeReference<'a> {
+    Hash(H256),
+    Inline(Rlp<'a>),
+    Empty,
+}
+
+impl<'a> NodeReference<'a> {
+    fn new(item: Rlp<'a>) -> Result<Self, LightClientError> {
+        if item.is_list() {
+            Ok(NodeReference::Inline(item))
+        } else if item.is_empty() {
+            Ok(NodeReference::Empty)
+        } else {
+            decode_hash(&item).map(NodeReference::Hash)
+        }
+    }
+}
+
+// Splits the hex prefix encoded path of a leaf or extension node into its kind and nibbles.
+fn decode_path(path: &[u8]) -> Result<(bool, Vec<u8>), LightClientError> {
+    let (flags, rest) = path
+        .split_first()
+        .ok_or(LightClientError::Decode { field: "trie node" })?;
+    let is_leaf = match flags >> 4 {
+        0 | 1 => false,
+        2 | 3 => true,
+        _ => return Err(LightClientError::Decode { field: "trie node" }),
+    };
+    let mut nibbles = Vec::with_capacity(rest.len() * 2 + 1);
+    // An odd number of nibbles stores the first one next to the flags.
+    if flags >> 4 & 1 == 1 {
+        nibbles.push(flags & 0x0f);
+    }
+    nibbles.extend(rest.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]));
+    Ok((is_leaf, nibbles))
+}
+
+pub(crate) fn decode_hash(item: &Rlp) -> Result<H256, LightClientError> {
+    let data = item.data().map_err(decode_error)?;
+    let hash: [u8; 32] = data.try_into().map_err(|_| LightClientError::InvalidLength {
+        expected: 32,
+        actual: data.len(),
+    })?;
+    Ok(H256(hash))
+}
+
+pub(crate) fn decode_uint256(item: &Rlp) -> Result<[u8; 32], LightClientError> {
+    let data = item.data().map_err(decode_error)?;
+    if data.len() > 32 {
+        return Err(LightClientError::InvalidLength {
+            expected: 32,
+            actual: data.len(),
+        });
+    }
+    let mut value = [0u8; 32];
+    value[32 - data.len()..].copy_from_slice(data);
+    Ok(value)
+}
+
+fn decode_error(_: rlp::DecoderError) -> LightClientError {
+    LightClientError::Decode { field: "trie node" }
+}
@@ -0,0 +1,31 @@
+use super::types::*;
+use super::utils::*;
+use crate::bls::{BlsVerifier, PreparedSyncCommittee};
+use crate::chain_spec::ChainSpec;
+use crate::error::LightClientError;
+use crate::policy::VerificationPolicy;
+use crate::hasher::Hasher;
+
+
+/// Verifies an optimistic header. Following the spec it only needs `policy.min_participants`
+/// signers, which is a single one by default, raise it to trust the header on less than finality.
+pub fn process_optimistic_update<V: BlsVerifier, H: Hasher>(update: LightClientOptimisticUpdate, sync_committee: &PreparedSyncCommittee<V>, current_slot: u64, policy: &VerificationPolicy, spec: &ChainSpec<H>) -> Result<LightClientHeader, LightClientError> {
+    if update.signature_slot <= update.attested_header.beacon.slot {
+        return Err(LightClientError::InvalidSlotOrder);
+    }
+    let sync_committee_bits = get_sync_committee_bits(&update.sync_aggregate.sync_committee_bits)?;
+    policy.verify_participation(get_sync_committee_sum(&sync_committee_bits))?;
+    policy.verify_update_age(update.signature_slot, current_slot)?;
+    verify_light_client_header(&update.attested_header, spec)?;
+
+    verify_signed_header(
+        sync_committee_bits,
+        update.sync_aggregate.sync_committee_signature,
+        sync_committee,
+        update.signature_slot,
+        update.attested_header.beacon.clone(),
+        spec,
+    )?;
+
+    Ok(update.attested_header)
+}
@@ -0,0 +1,93 @@
+//! Participation and freshness rules for accepting signed updates.
+//!
+//! The spec accepts an update signed by `MIN_SYNC_COMMITTEE_PARTICIPANTS` members and only needs
+//! a 2/3 supermajority to finalize headers and sync committees from it. Deployments that want a
+//! different safety margin set it here, every processing function checks participation through
+//! the same [`VerificationPolicy`].
+
+use super::types::*;
+use crate::error::LightClientError;
+
+/// Thresholds an update has to meet before its signature is even checked.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct VerificationPolicy {
+    /// Fewest sync committee members that have to sign for an update to be accepted.
+    pub min_participants: u64,
+    /// Fewest sync committee members that have to sign for an update to finalize its finalized
+    /// header or its next sync committee.
+    pub finality_threshold: u64,
+    /// Most slots the signature slot of a fresh header may lie behind the current slot, `None`
+    /// for no limit. Sync committee period updates are walked through the past and aren't aged.
+    pub max_update_age: Option<u64>,
+}
+
+impl VerificationPolicy {
+    /// The thresholds of the consensus spec, with no limit on the age of an update.
+    pub const fn spec() -> Self {
+        VerificationPolicy {
+            min_participants: MIN_SYNC_COMMITTEE_PARTICIPANTS,
+            finality_threshold: supermajority_threshold(SYNC_COMMITTEE_SIZE as u64),
+            max_update_age: None,
+        }
+    }
+
+    /// Checks that enough members signed for the update to be accepted.
+    pub fn verify_participation(&self, participants: u64) -> Result<(), LightClientError> {
+        if participants < self.min_participants {
+            return Err(LightClientError::InsufficientParticipation {
+                participants,
+                required: self.min_participants,
+            });
+        }
+        Ok(())
+    }
+
+    /// Whether enough members signed for the update to finalize anything.
+    pub fn is_finalizing(&self, participants: u64) -> bool {
+        participants >= self.min_participants && participants >= self.finality_threshold
+    }
+
+    /// Checks that enough members signed for the update to finalize anything.
+    pub fn verify_finality_participation(&self, participants: u64) -> Result<(), LightClientError> {
+        self.verify_participation(participants)?;
+        if !self.is_finalizing(participants) {
+            return Err(LightClientError::InsufficientParticipation {
+                participants,
+                required: self.finality_threshold,
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks that an update signed at `signature_slot` isn't from the future nor older than
+    /// `max_update_age` at `current_slot`.
+    pub fn verify_update_age(
+        &self,
+        signature_slot: u64,
+        current_slot: u64,
+    ) -> Result<(), LightClientError> {
+        if signature_slot > current_slot {
+            return Err(LightClientError::InvalidSlotOrder);
+        }
+        match self.max_update_age {
+            Some(max_update_age) if current_slot - signature_slot > max_update_age => {
+                Err(LightClientError::StaleUpdate {
+                    signature_slot,
+                    current_slot,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for VerificationPolicy {
+    fn default() -> Self {
+        VerificationPolicy::spec()
+    }
+}
+
+// Smallest number of participants that satisfies `participants * 3 >= committee_size * 2`.
+const fn supermajority_threshold(committee_size: u64) -> u64 {
+    (committee_size * 2 + 2) / 3
+}
@@ -0,0 +1,32 @@
+//! Consensus preset values that change the shape of the SSZ containers.
+//!
+//! The sync committee size is a const generic argument of `SSZSyncCommittee` and
+//! `SSZSyncAggregate`, so the preset is chosen at build time with the `mainnet` (default) or
+//! `minimal` cargo feature. Exactly one of them has to be enabled.
+
+#[cfg(all(feature = "mainnet", feature = "minimal"))]
+compile_error!("the `mainnet` and `minimal` features are mutually exclusive");
+
+#[cfg(not(any(feature = "mainnet", feature = "minimal")))]
+compile_error!("one of the `mainnet` or `minimal` features has to be enabled");
+
+pub mod mainnet {
+    pub const SYNC_COMMITTEE_SIZE: usize = 512;
+    pub const SLOTS_PER_EPOCH: u64 = 32;
+    pub const EPOCHS_PER_SYNC_COMMITTEE_PERIOD: u64 = 256;
+    pub const SLOTS_PER_HISTORICAL_ROOT: u64 = 8192;
+    pub const IS_MINIMAL: bool = false;
+}
+
+pub mod minimal {
+    pub const SYNC_COMMITTEE_SIZE: usize = 32;
+    pub const SLOTS_PER_EPOCH: u64 = 8;
+    pub const EPOCHS_PER_SYNC_COMMITTEE_PERIOD: u64 = 8;
+    pub const SLOTS_PER_HISTORICAL_ROOT: u64 = 64;
+    pub const IS_MINIMAL: bool = true;
+}
+
+#[cfg(not(feature = "minimal"))]
+pub use mainnet::*;
+#[cfg(feature = "minimal")]
+pub use minimal::*;
@@ -0,0 +1,169 @@
+//! Receipt inclusion proofs, for proving that a log was emitted in a finalized block.
+//!
+//! The receipts trie of a block maps the RLP encoded index of each transaction to its receipt.
+//! Legacy receipts are stored as their RLP list, typed receipts (EIP-2718) as the transaction
+//! type byte followed by the same list.
+
+use super::types::*;
+use crate::chain_spec::ChainSpec;
+use crate::error::LightClientError;
+use crate::mpt::{decode_hash, verify_proof};
+use alloc::vec::Vec;
+use rlp::Rlp;
+use crate::hasher::Hasher;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransactionType {
+    Legacy,
+    // EIP-2930
+    AccessList,
+    // EIP-1559
+    DynamicFee,
+    // EIP-4844
+    Blob,
+    // EIP-7702, part of Prague alongside Electra.
+    SetCode,
+}
+
+impl TransactionType {
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            1 => Some(TransactionType::AccessList),
+            2 => Some(TransactionType::DynamicFee),
+            3 => Some(TransactionType::Blob),
+            4 => Some(TransactionType::SetCode),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Log {
+    pub address: [u8; 20],
+    pub topics: Vec<H256>,
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Receipt {
+    pub transaction_type: TransactionType,
+    // Whether the transaction succeeded, receipts from before Byzantium aren't supported.
+    pub success: bool,
+    pub cumulative_gas_used: u64,
+    pub logs_bloom: Vec<u8>,
+    pub logs: Vec<Log>,
+}
+
+impl Receipt {
+    /// Decodes a receipt as it is stored in the receipts trie.
+    pub fn decode(bytes: &[u8]) -> Result<Self, LightClientError> {
+        let (transaction_type, payload) = match bytes.first() {
+            // An RLP list starts at 0xc0, a transaction type is below 0x80.
+            Some(byte) if *byte >= 0xc0 => (TransactionType::Legacy, bytes),
+            Some(byte) => (
+                TransactionType::from_byte(*byte).ok_or_else(decode_error)?,
+                &bytes[1..],
+            ),
+            None => return Err(decode_error()),
+        };
+
+        let rlp = Rlp::new(payload);
+        if rlp.item_count().map_err(|_| decode_error())? != 4 {
+            return Err(decode_error());
+        }
+        let success = match item_data(&rlp, 0)? {
+            [] => false,
+            [1] => true,
+            _ => return Err(decode_error()),
+        };
+        let logs_bloom = item_data(&rlp, 2)?;
+        if logs_bloom.len() != BYTES_PER_LOGS_BLOOM {
+            return Err(LightClientError::InvalidLength {
+                expected: BYTES_PER_LOGS_BLOOM,
+                actual: logs_bloom.len(),
+            });
+        }
+        let logs = rlp
+            .at(3)
+            .map_err(|_| decode_error())?
+            .iter()
+            .map(|log| decode_log(&log))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Receipt {
+            transaction_type,
+            success,
+            cumulative_gas_used: rlp.val_at(1).map_err(|_| decode_error())?,
+            logs_bloom: logs_bloom.to_vec(),
+            logs,
+        })
+    }
+
+    /// The logs emitted by the contract at `address` with `topic` as their first topic, which is
+    /// the event signature for all but anonymous events.
+    pub fn logs_with_topic<'a>(
+        &'a self,
+        address: &'a [u8; 20],
+        topic: &'a H256,
+    ) -> impl Iterator<Item = &'a Log> + 'a {
+        self.logs
+            .iter()
+            .filter(move |log| log.address == *address && log.topics.first() == Some(topic))
+    }
+}
+
+/// Verifies the proof of the receipt of the transaction at `transaction_index` in the receipts
+/// trie with root `receipts_root`, `None` if the proof shows that the block has no such
+/// transaction.
+pub fn verify_receipt_proof<P: AsRef<[u8]>>(
+    receipts_root: H256,
+    transaction_index: u64,
+    proof: &[P],
+) -> Result<Option<Receipt>, LightClientError> {
+    verify_proof(receipts_root, &rlp::encode(&transaction_index), proof)?
+        .map(|value| Receipt::decode(&value))
+        .transpose()
+}
+
+/// Verifies the proof of a receipt against the receipts root of `header`, after checking its
+/// execution payload header against the beacon block body.
+pub fn verify_receipt_proof_at_header<P: AsRef<[u8]>, H: Hasher>(
+    header: &LightClientHeader,
+    transaction_index: u64,
+    proof: &[P],
+    spec: &ChainSpec<H>,
+) -> Result<Option<Receipt>, LightClientError> {
+    let execution = header.verified_execution(spec)?;
+    verify_receipt_proof(execution.receipts_root, transaction_index, proof)
+}
+
+fn decode_log(log: &Rlp) -> Result<Log, LightClientError> {
+    if log.item_count().map_err(|_| decode_error())? != 3 {
+        return Err(decode_error());
+    }
+    let address = item_data(log, 0)?;
+    let topics = log
+        .at(1)
+        .map_err(|_| decode_error())?
+        .iter()
+        .map(|topic| decode_hash(&topic))
+        .collect::<Result<_, _>>()?;
+    Ok(Log {
+        address: address.try_into().map_err(|_| LightClientError::InvalidLength {
+            expected: 20,
+            actual: address.len(),
+        })?,
+        topics,
+        data: item_data(log, 2)?.to_vec(),
+    })
+}
+
+fn item_data<'a>(rlp: &Rlp<'a>, index: usize) -> Result<&'a [u8], LightClientError> {
+    rlp.at(index)
+        .and_then(|item| item.data())
+        .map_err(|_| decode_error())
+}
+
+fn decode_error() -> LightClientError {
+    LightClientError::Decode { field: "receipt" }
+}
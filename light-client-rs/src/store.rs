@@ -0,0 +1,395 @@
+//! Stateful light client following the consensus-spec `LightClientStore`.
+//!
+//! Unlike [`crate::process_sync_committee_period_update`], which only checks a single
+//! transition between two updates, the store keeps track of the finalized and optimistic
+//! headers and both sync committees so that a long running process can feed it updates as they
+//! arrive.
+//!
+//! Both sync committees are kept as [`PreparedSyncCommittee`]s. Their pubkeys are decompressed
+//! once when the store learns about a committee and reused for every update signed by it.
+
+use super::types::*;
+use super::utils::*;
+use crate::bls::{BlsVerifier, DefaultBlsVerifier, PreparedSyncCommittee};
+use crate::chain_spec::ChainSpec;
+use crate::error::{LightClientError, MerkleBranch};
+use crate::policy::VerificationPolicy;
+use crate::hasher::Hasher;
+
+#[derive(Clone, Debug)]
+pub struct LightClientStore<V: BlsVerifier = DefaultBlsVerifier> {
+    // Header that is finalized
+    pub finalized_header: LightClientHeader,
+    // Sync committees corresponding to the finalized header
+    pub current_sync_committee: PreparedSyncCommittee<V>,
+    pub next_sync_committee: Option<PreparedSyncCommittee<V>>,
+    // Best available header to switch finalized head to if we see nothing else
+    pub best_valid_update: Option<SyncCommitteePeriodUpdate>,
+    // Most recent available reasonably-safe header
+    pub optimistic_header: LightClientHeader,
+    // Max number of active participants in a sync committee (used to calculate safety threshold)
+    pub previous_max_active_participants: u64,
+    pub current_max_active_participants: u64,
+}
+
+impl<V: BlsVerifier> LightClientStore<V> {
+    /// Fails if a pubkey of `current_sync_committee` isn't a valid point.
+    pub fn new(
+        finalized_header: LightClientHeader,
+        current_sync_committee: SyncCommittee,
+    ) -> Result<Self, LightClientError> {
+        Ok(LightClientStore {
+            optimistic_header: finalized_header.clone(),
+            finalized_header,
+            current_sync_committee: PreparedSyncCommittee::new(current_sync_committee)?,
+            next_sync_committee: None,
+            best_valid_update: None,
+            previous_max_active_participants: 0,
+            current_max_active_participants: 0,
+        })
+    }
+
+    pub fn is_next_sync_committee_known(&self) -> bool {
+        self.next_sync_committee.is_some()
+    }
+
+    pub fn get_safety_threshold(&self) -> u64 {
+        core::cmp::max(
+            self.previous_max_active_participants,
+            self.current_max_active_participants,
+        ) / 2
+    }
+}
+
+pub fn is_sync_committee_update(update: &SyncCommitteePeriodUpdate) -> bool {
+    update
+        .next_sync_committee_branch
+        .iter()
+        .any(|node| *node != H256::default())
+}
+
+pub fn is_finality_update(update: &SyncCommitteePeriodUpdate) -> bool {
+    update
+        .finality_branch
+        .iter()
+        .any(|node| *node != H256::default())
+}
+
+// Absent headers decode from the post-Capella containers with an all-zero execution payload.
+fn is_empty_light_client_header(header: &LightClientHeader) -> bool {
+    header.beacon == BeaconHeader::default()
+        && header
+            .execution
+            .as_ref()
+            .map_or(true, ExecutionPayloadHeader::is_empty)
+        && header.execution_branch.iter().all(|node| *node == H256::default())
+}
+
+// Absent committees decode from SSZ as all-zero pubkeys rather than an empty list.
+fn is_empty_sync_committee(sync_committee: &SyncCommittee) -> bool {
+    sync_committee
+        .pubkeys
+        .iter()
+        .chain(core::iter::once(&sync_committee.aggregate_pubkey))
+        .all(|pubkey| pubkey.0 == [0u8; PUBKEY_SIZE])
+}
+
+pub fn validate_light_client_update<V: BlsVerifier, H: Hasher>(
+    store: &LightClientStore<V>,
+    update: &SyncCommitteePeriodUpdate,
+    current_slot: u64,
+    policy: &VerificationPolicy,
+    spec: &ChainSpec<H>,
+) -> Result<(), LightClientError> {
+    // Verify sync committee has sufficient participants
+    let sync_committee_bits =
+        get_sync_committee_bits(&update.sync_aggregate.sync_committee_bits)?;
+    policy.verify_participation(get_sync_committee_sum(&sync_committee_bits))?;
+
+    // Verify update does not skip a sync committee period
+    verify_light_client_header(&update.attested_header, spec)?;
+    let update_attested_slot = update.attested_header.beacon.slot;
+    let update_finalized_slot = update.finalized_header.beacon.slot;
+    let update_signature_slot = update.signature_slot;
+    if !(current_slot >= update_signature_slot
+        && update_signature_slot > update_attested_slot
+        && update_attested_slot >= update_finalized_slot)
+    {
+        return Err(LightClientError::InvalidSlotOrder);
+    }
+    policy.verify_update_age(update_signature_slot, current_slot)?;
+    let store_period = compute_sync_committee_period_at_slot(store.finalized_header.beacon.slot);
+    let update_signature_period = compute_sync_committee_period_at_slot(update_signature_slot);
+    let period_is_valid = if store.is_next_sync_committee_known() {
+        update_signature_period == store_period || update_signature_period == store_period + 1
+    } else {
+        update_signature_period == store_period
+    };
+    if !period_is_valid {
+        return Err(LightClientError::InvalidSyncCommitteePeriod {
+            store_period,
+            update_period: update_signature_period,
+        });
+    }
+
+    // Verify update is relevant
+    let update_attested_period = compute_sync_committee_period_at_slot(update_attested_slot);
+    let update_has_next_sync_committee = !store.is_next_sync_committee_known()
+        && is_sync_committee_update(update)
+        && update_attested_period == store_period;
+    if !(update_attested_slot > store.finalized_header.beacon.slot || update_has_next_sync_committee) {
+        return Err(LightClientError::IrrelevantUpdate);
+    }
+
+    // Verify that the `finality_branch`, if present, confirms `finalized_header`
+    // to match the finalized checkpoint root saved in the state of `attested_header`.
+    // Note that the genesis finalized checkpoint root is represented as a zero hash.
+    if !is_finality_update(update) {
+        if !is_empty_light_client_header(&update.finalized_header) {
+            return Err(LightClientError::UnexpectedFinalizedHeader);
+        }
+    } else {
+        let finalized_root = if update_finalized_slot == GENESIS_SLOT {
+            if !is_empty_light_client_header(&update.finalized_header) {
+                return Err(LightClientError::UnexpectedFinalizedHeader);
+            }
+            H256::default()
+        } else {
+            verify_light_client_header(&update.finalized_header, spec)?;
+            update.finalized_header.beacon.hash_tree_root_with::<H>()
+        };
+        let gindex = finalized_root_gindex(spec, update_attested_slot);
+        verify_header::<H>(
+            finalized_root,
+            update.finality_branch.clone(),
+            update.attested_header.beacon.state_root,
+            gindex,
+            MerkleBranch::Finality,
+        )?;
+    }
+
+    // Verify that the `next_sync_committee`, if present, actually is the next sync committee
+    // saved in the state of the `attested_header`
+    if !is_sync_committee_update(update) {
+        if !is_empty_sync_committee(&update.next_sync_committee) {
+            return Err(LightClientError::UnexpectedNextSyncCommittee);
+        }
+    } else {
+        if update_attested_period == store_period {
+            if let Some(next_sync_committee) = &store.next_sync_committee {
+                if update.next_sync_committee != *next_sync_committee.sync_committee() {
+                    return Err(LightClientError::NextSyncCommitteeMismatch);
+                }
+            }
+        }
+        let gindex = next_sync_committee_gindex(spec, update_attested_slot);
+        verify_sync_committee::<H>(
+            &update.next_sync_committee,
+            update.next_sync_committee_branch.clone(),
+            update.attested_header.beacon.state_root,
+            gindex,
+            MerkleBranch::NextSyncCommittee,
+        )?;
+    }
+
+    // Verify sync committee aggregate signature
+    let sync_committee = if update_signature_period == store_period {
+        &store.current_sync_committee
+    } else {
+        // Checked above: the next committee is known whenever the signature period moved on.
+        store
+            .next_sync_committee
+            .as_ref()
+            .ok_or(LightClientError::InvalidSyncCommitteePeriod {
+                store_period,
+                update_period: update_signature_period,
+            })?
+    };
+    verify_signed_header(
+        sync_committee_bits,
+        update.sync_aggregate.sync_committee_signature.clone(),
+        sync_committee,
+        update_signature_slot,
+        update.attested_header.beacon.clone(),
+        spec,
+    )
+}
+
+pub fn apply_light_client_update<V: BlsVerifier>(
+    store: &mut LightClientStore<V>,
+    update: &SyncCommitteePeriodUpdate,
+) -> Result<(), LightClientError> {
+    let store_period = compute_sync_committee_period_at_slot(store.finalized_header.beacon.slot);
+    let update_finalized_period =
+        compute_sync_committee_period_at_slot(update.finalized_header.beacon.slot);
+    if !store.is_next_sync_committee_known() {
+        if update_finalized_period != store_period {
+            return Err(LightClientError::InvalidSyncCommitteePeriod {
+                store_period,
+                update_period: update_finalized_period,
+            });
+        }
+        store.next_sync_committee = Some(PreparedSyncCommittee::new(
+            update.next_sync_committee.clone(),
+        )?);
+    } else if update_finalized_period == store_period + 1 {
+        // Prepare the new committee first, the store must not rotate halfway.
+        let next_sync_committee = PreparedSyncCommittee::new(update.next_sync_committee.clone())?;
+        if let Some(current_sync_committee) = store.next_sync_committee.take() {
+            store.current_sync_committee = current_sync_committee;
+        }
+        store.next_sync_committee = Some(next_sync_committee);
+        store.previous_max_active_participants = store.current_max_active_participants;
+        store.current_max_active_participants = 0;
+    }
+    if update.finalized_header.beacon.slot > store.finalized_header.beacon.slot {
+        store.finalized_header = update.finalized_header.clone();
+        if store.finalized_header.beacon.slot > store.optimistic_header.beacon.slot {
+            store.optimistic_header = store.finalized_header.clone();
+        }
+    }
+    Ok(())
+}
+
+pub fn process_light_client_store_force_update<V: BlsVerifier>(
+    store: &mut LightClientStore<V>,
+    current_slot: u64,
+) -> Result<(), LightClientError> {
+    if current_slot > store.finalized_header.beacon.slot + UPDATE_TIMEOUT {
+        if let Some(mut best_valid_update) = store.best_valid_update.clone() {
+            // Forced best update when the update timeout has elapsed.
+            // Because the apply logic waits for `finalized_header.beacon.slot` to indicate sync
+            // committee finality, the `attested_header` may be treated as `finalized_header` in
+            // extended periods of non-finality to guarantee progression into later sync
+            // committee periods according to `is_better_update`.
+            if best_valid_update.finalized_header.beacon.slot <= store.finalized_header.beacon.slot {
+                best_valid_update.finalized_header = best_valid_update.attested_header.clone();
+            }
+            // Only forget the update once it is applied, so that a failure can be retried.
+            apply_light_client_update(store, &best_valid_update)?;
+            store.best_valid_update = None;
+        }
+    }
+    Ok(())
+}
+
+pub fn process_light_client_update<V: BlsVerifier, H: Hasher>(
+    store: &mut LightClientStore<V>,
+    update: SyncCommitteePeriodUpdate,
+    current_slot: u64,
+    policy: &VerificationPolicy,
+    spec: &ChainSpec<H>,
+) -> Result<(), LightClientError> {
+    validate_light_client_update(store, &update, current_slot, policy, spec)?;
+
+    let sync_committee_bits =
+        get_sync_committee_bits(&update.sync_aggregate.sync_committee_bits)?;
+    let participants = get_sync_committee_sum(&sync_committee_bits);
+
+    // Track the maximum number of active participants in the committee signatures
+    store.current_max_active_participants =
+        core::cmp::max(store.current_max_active_participants, participants);
+
+    // Update the optimistic header
+    if participants > store.get_safety_threshold()
+        && update.attested_header.beacon.slot > store.optimistic_header.beacon.slot
+    {
+        store.optimistic_header = update.attested_header.clone();
+    }
+
+    // Update finalized header
+    let update_has_finalized_next_sync_committee = !store.is_next_sync_committee_known()
+        && is_sync_committee_update(&update)
+        && is_finality_update(&update)
+        && compute_sync_committee_period_at_slot(update.finalized_header.beacon.slot)
+            == compute_sync_committee_period_at_slot(update.attested_header.beacon.slot);
+    if policy.is_finalizing(participants)
+        && (update.finalized_header.beacon.slot > store.finalized_header.beacon.slot
+            || update_has_finalized_next_sync_committee)
+    {
+        // Normal update through the finality threshold
+        apply_light_client_update(store, &update)?;
+        store.best_valid_update = None;
+    } else if store
+        .best_valid_update
+        .as_ref()
+        .map_or(true, |best| is_better_update(&update, best))
+    {
+        // Remember the best update in case we have to force-update to it if the timeout elapses
+        store.best_valid_update = Some(update);
+    }
+    Ok(())
+}
+
+/// Whether `new_update` should replace `old_update` as the best update of a period, following
+/// the spec's ranking: supermajority participation, a next sync committee relevant to the signature
+/// period, finality, finality of that committee, participation, then the older update.
+pub fn is_better_update(
+    new_update: &SyncCommitteePeriodUpdate,
+    old_update: &SyncCommitteePeriodUpdate,
+) -> bool {
+    // Compare supermajority (> 2/3) sync committee participation
+    let max_active_participants = SYNC_COMMITTEE_SIZE as u64;
+    let new_num_active_participants = active_participants(new_update);
+    let old_num_active_participants = active_participants(old_update);
+    let new_has_supermajority = new_num_active_participants * 3 >= max_active_participants * 2;
+    let old_has_supermajority = old_num_active_participants * 3 >= max_active_participants * 2;
+    if new_has_supermajority != old_has_supermajority {
+        return new_has_supermajority;
+    }
+    if !new_has_supermajority && new_num_active_participants != old_num_active_participants {
+        return new_num_active_participants > old_num_active_participants;
+    }
+
+    // Compare presence of relevant sync committee
+    let has_relevant_sync_committee = |update: &SyncCommitteePeriodUpdate| {
+        is_sync_committee_update(update)
+            && compute_sync_committee_period_at_slot(update.attested_header.beacon.slot)
+                == compute_sync_committee_period_at_slot(update.signature_slot)
+    };
+    let new_has_relevant_sync_committee = has_relevant_sync_committee(new_update);
+    let old_has_relevant_sync_committee = has_relevant_sync_committee(old_update);
+    if new_has_relevant_sync_committee != old_has_relevant_sync_committee {
+        return new_has_relevant_sync_committee;
+    }
+
+    // Compare indication of any finality
+    let new_has_finality = is_finality_update(new_update);
+    let old_has_finality = is_finality_update(old_update);
+    if new_has_finality != old_has_finality {
+        return new_has_finality;
+    }
+
+    // Compare sync committee finality
+    if new_has_finality {
+        let has_sync_committee_finality = |update: &SyncCommitteePeriodUpdate| {
+            compute_sync_committee_period_at_slot(update.finalized_header.beacon.slot)
+                == compute_sync_committee_period_at_slot(update.attested_header.beacon.slot)
+        };
+        let new_has_sync_committee_finality = has_sync_committee_finality(new_update);
+        let old_has_sync_committee_finality = has_sync_committee_finality(old_update);
+        if new_has_sync_committee_finality != old_has_sync_committee_finality {
+            return new_has_sync_committee_finality;
+        }
+    }
+
+    // Tiebreaker 1: Sync committee participation beyond supermajority
+    if new_num_active_participants != old_num_active_participants {
+        return new_num_active_participants > old_num_active_participants;
+    }
+
+    // Tiebreaker 2: Prefer older data (fewer changes to best)
+    if new_update.attested_header.beacon.slot != old_update.attested_header.beacon.slot {
+        return new_update.attested_header.beacon.slot < old_update.attested_header.beacon.slot;
+    }
+    new_update.signature_slot < old_update.signature_slot
+}
+
+fn active_participants(update: &SyncCommitteePeriodUpdate) -> u64 {
+    update
+        .sync_aggregate
+        .sync_committee_bits
+        .iter()
+        .map(|bit| if bit == true { 1 } else { 0 })
+        .sum()
+}
@@ -1,16 +1,23 @@
 use ssz_rs::deserialize;
-use crate::alloc::string::ToString;
 use alloc::vec;
 use alloc::vec::Vec;
-use sha2::{Digest, Sha256};
-use alloc::string::String;
+use crate::chain_spec::{ChainSpec, ForkName};
+use crate::error::{LightClientError, MerkleBranch};
+use crate::merkle::{get_generalized_index_length, GeneralizedIndex};
+use crate::utils::{
+    current_sync_committee_gindex, finalized_root_gindex, hash_tree_root_beacon_header,
+    hash_tree_root_sync_committee, next_sync_committee_gindex, verify_light_client_header,
+};
 
+#[cfg(feature = "milagro")]
 pub use milagro_bls::{AggregatePublicKey, AggregateSignature, AmclError, Signature};
 // pub use snowbridge_ethereum::H256;
 pub use ssz_rs::{
-    prelude::Vector, Bitvector, Deserialize, SimpleSerialize as SimpleSerializeTrait, Sized,
+    prelude::{List, Vector},
+    Bitvector, Deserialize, SimpleSerialize as SimpleSerializeTrait, Sized,
 };
 use ssz_rs_derive::SimpleSerialize;
+use crate::hasher::{DefaultHasher, Hasher};
 macro_rules! tryprintln {
     ($body:expr) => {
         // tryprintln!(body)
@@ -22,19 +29,48 @@ macro_rules! tryprintln {
 // use alloc::vec::Vec;
 // use alloc::format;
 pub type ForkVersion = [u8; 4];
-pub const SYNC_COMMITTEE_SIZE: usize = 512;
 pub const PUBKEY_SIZE: usize = 48;
 pub const SIGNATURE_SIZE: usize = 96;
 pub const NEXT_SYNC_COMMITTEE_DEPTH: u64 = 5;
 pub const NEXT_SYNC_COMMITTEE_INDEX: u64 = 23;
+pub const CURRENT_SYNC_COMMITTEE_DEPTH: u64 = 5;
+pub const CURRENT_SYNC_COMMITTEE_INDEX: u64 = 22;
 pub const FINALIZED_ROOT_DEPTH: u64 = 6;
 pub const FINALIZED_ROOT_INDEX: u64 = 41;
+// Electra grew the beacon state past 32 fields, adding a level to the state tree.
+pub const NEXT_SYNC_COMMITTEE_DEPTH_ELECTRA: u64 = 6;
+pub const NEXT_SYNC_COMMITTEE_INDEX_ELECTRA: u64 = 23;
+pub const CURRENT_SYNC_COMMITTEE_DEPTH_ELECTRA: u64 = 6;
+pub const CURRENT_SYNC_COMMITTEE_INDEX_ELECTRA: u64 = 22;
+pub const FINALIZED_ROOT_DEPTH_ELECTRA: u64 = 7;
+pub const FINALIZED_ROOT_INDEX_ELECTRA: u64 = 41;
+pub const EXECUTION_PAYLOAD_DEPTH: u64 = 4;
+pub const EXECUTION_PAYLOAD_INDEX: u64 = 9;
+// Generalized indices of the proven fields, `(1 << depth) + index`.
+pub const NEXT_SYNC_COMMITTEE_GINDEX: GeneralizedIndex = 55;
+pub const CURRENT_SYNC_COMMITTEE_GINDEX: GeneralizedIndex = 54;
+pub const FINALIZED_ROOT_GINDEX: GeneralizedIndex = 105;
+pub const NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA: GeneralizedIndex = 87;
+pub const CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA: GeneralizedIndex = 86;
+pub const FINALIZED_ROOT_GINDEX_ELECTRA: GeneralizedIndex = 169;
+pub const EXECUTION_PAYLOAD_GINDEX: GeneralizedIndex = 25;
+// Fields of the beacon state that ancestry proofs go through, Electra adds a level as above.
+pub const BLOCK_ROOTS_GINDEX: GeneralizedIndex = 37;
+pub const HISTORICAL_SUMMARIES_GINDEX: GeneralizedIndex = 59;
+pub const BLOCK_ROOTS_GINDEX_ELECTRA: GeneralizedIndex = 69;
+pub const HISTORICAL_SUMMARIES_GINDEX_ELECTRA: GeneralizedIndex = 91;
+pub const HISTORICAL_ROOTS_LIMIT: u64 = 1 << 24;
+pub const BYTES_PER_LOGS_BLOOM: usize = 256;
+pub const MAX_EXTRA_DATA_BYTES: usize = 32;
 pub const DOMAIN_SYNC_COMMITTEE: [u8; 4] = [7, 0, 0, 0];
-pub const GENESIS_FORK_VERSION: ForkVersion = [30, 30, 30, 30];
 
-pub const SLOTS_PER_EPOCH: u64 = 32;
-pub const EPOCHS_PER_SYNC_COMMITTEE_PERIOD: u64 = 256;
-pub const IS_MINIMAL: bool = false;
+pub use crate::preset::{
+    EPOCHS_PER_SYNC_COMMITTEE_PERIOD, IS_MINIMAL, SLOTS_PER_EPOCH, SLOTS_PER_HISTORICAL_ROOT,
+    SYNC_COMMITTEE_SIZE,
+};
+pub const GENESIS_SLOT: u64 = 0;
+pub const MIN_SYNC_COMMITTEE_PARTICIPANTS: u64 = 1;
+pub const UPDATE_TIMEOUT: u64 = SLOTS_PER_EPOCH * EPOCHS_PER_SYNC_COMMITTEE_PERIOD;
 
 pub type Domain = H256;
 pub type Root = H256;
@@ -42,15 +78,6 @@ pub type Root = H256;
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
 pub struct H256(pub [u8; 32]);
 
-pub(super) fn sha2_256(data: &[u8]) -> H256 {
-    let mut hasher = Sha256::new();
-    hasher.update(data);
-    let result = hasher.finalize();
-    let mut h = H256::default();
-    h.0.copy_from_slice(&result);
-    h
-}
-
 impl From<[u8; 32]> for H256 {
     fn from(bytes: [u8; 32]) -> Self {
         H256(bytes)
@@ -64,9 +91,12 @@ impl From<H256> for [u8; 32] {
 }
 
 impl H256 {
-    pub fn from_slice(bytes: &[u8]) -> Result<Self, String> {
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, LightClientError> {
         if bytes.len() != 32 {
-            return Err("Invalid length for H256".into());
+            return Err(LightClientError::InvalidLength {
+                expected: 32,
+                actual: bytes.len(),
+            });
         }
         let mut h = H256::default();
         h.0.copy_from_slice(bytes);
@@ -81,12 +111,15 @@ impl H256 {
 #[derive(Clone, PartialEq, Debug)]
 pub struct PublicKey(pub [u8; 48]);
 
-#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct BeaconHeader {
     // The slot for which this block is created. Must be greater than the slot of the block defined
     // by parentRoot.
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::beacon_api::from_quoted_u64"))]
     pub slot: u64,
     // The index of the validator that proposed the block.
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::beacon_api::from_quoted_u64"))]
     pub proposer_index: u64,
     // The block root of the parent block, forming a block chain.
     pub parent_root: Root,
@@ -96,11 +129,61 @@ pub struct BeaconHeader {
     pub body_root: Root,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExecutionPayloadHeader {
+    pub parent_hash: H256,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::beacon_api::from_hex_to_array"))]
+    pub fee_recipient: [u8; 20],
+    pub state_root: H256,
+    pub receipts_root: H256,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::beacon_api::from_hex_to_bytes"))]
+    pub logs_bloom: Vec<u8>,
+    pub prev_randao: H256,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::beacon_api::from_quoted_u64"))]
+    pub block_number: u64,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::beacon_api::from_quoted_u64"))]
+    pub gas_limit: u64,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::beacon_api::from_quoted_u64"))]
+    pub gas_used: u64,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::beacon_api::from_quoted_u64"))]
+    pub timestamp: u64,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::beacon_api::from_hex_to_bytes"))]
+    pub extra_data: Vec<u8>,
+    // uint256, little endian as in its SSZ encoding
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::beacon_api::from_quoted_u256"))]
+    pub base_fee_per_gas: [u8; 32],
+    pub block_hash: H256,
+    pub transactions_root: H256,
+    pub withdrawals_root: H256,
+    // Only part of the header from Deneb on, zero before.
+    #[cfg_attr(feature = "serde", serde(default, deserialize_with = "crate::beacon_api::from_quoted_u64"))]
+    pub blob_gas_used: u64,
+    #[cfg_attr(feature = "serde", serde(default, deserialize_with = "crate::beacon_api::from_quoted_u64"))]
+    pub excess_blob_gas: u64,
+}
+
+/// A beacon header together with the execution payload header of its block.
+///
+/// Before Capella light client headers only carry the beacon header, `execution` is `None` and
+/// the branch is empty.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LightClientHeader {
+    pub beacon: BeaconHeader,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub execution: Option<ExecutionPayloadHeader>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub execution_branch: Vec<H256>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct SyncAggregate {
     // both of these were bounded vecs
-    // #[cfg_attr(feature = "std", serde(deserialize_with = "from_hex_to_bytes"))]
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::beacon_api::from_hex_to_bitvector"))]
     pub sync_committee_bits: Bitvector<SYNC_COMMITTEE_SIZE>,
-    // #[cfg_attr(feature = "std", serde(deserialize_with = "from_hex_to_bytes"))]
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::beacon_api::from_hex_to_bytes"))]
     pub sync_committee_signature: Vec<u8>,
 }
 #[derive(Clone, Default, PartialEq)]
@@ -154,8 +237,13 @@ pub struct SSZSyncCommitteePeriodUpdate {
     // was a bounded vec
     pub finality_branch: Vector<[u8; 32], 6>,
     pub sync_aggregate: SSZSyncAggregate,
-    // #[cfg_attr(feature = "std", serde(deserialize_with = "from_hex_to_fork_version"))]
-    pub fork_version: ForkVersion,
+    pub signature_slot: u64,
+}
+#[derive(Default, SimpleSerialize)]
+pub struct SSZLightClientBootstrap {
+    pub header: SSZBeaconBlockHeader,
+    pub current_sync_committee: SSZSyncCommittee,
+    pub current_sync_committee_branch: Vector<[u8; 32], 5>,
 }
 #[derive(Default, SimpleSerialize)]
 pub struct SSZFinalizedHeaderUpdate {
@@ -163,43 +251,469 @@ pub struct SSZFinalizedHeaderUpdate {
     pub finalized_header: SSZBeaconBlockHeader,
     pub finality_branch: Vector<[u8; 32], 6>,
     pub sync_aggregate: SSZSyncAggregate,
+    pub signature_slot: u64,
 }
-#[derive(Clone, Debug)]
+#[derive(Default, SimpleSerialize)]
+pub struct SSZLightClientOptimisticUpdate {
+    pub attested_header: SSZBeaconBlockHeader,
+    pub sync_aggregate: SSZSyncAggregate,
+    pub signature_slot: u64,
+}
+#[derive(Default, SimpleSerialize, Clone, Debug)]
+pub struct SSZExecutionPayloadHeaderCapella {
+    pub parent_hash: [u8; 32],
+    pub fee_recipient: Vector<u8, 20>,
+    pub state_root: [u8; 32],
+    pub receipts_root: [u8; 32],
+    pub logs_bloom: Vector<u8, BYTES_PER_LOGS_BLOOM>,
+    pub prev_randao: [u8; 32],
+    pub block_number: u64,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    pub timestamp: u64,
+    pub extra_data: List<u8, MAX_EXTRA_DATA_BYTES>,
+    // uint256 shares its encoding and hash tree root with 32 little endian bytes
+    pub base_fee_per_gas: [u8; 32],
+    pub block_hash: [u8; 32],
+    pub transactions_root: [u8; 32],
+    pub withdrawals_root: [u8; 32],
+}
+#[derive(Default, SimpleSerialize, Clone, Debug)]
+pub struct SSZExecutionPayloadHeaderDeneb {
+    pub parent_hash: [u8; 32],
+    pub fee_recipient: Vector<u8, 20>,
+    pub state_root: [u8; 32],
+    pub receipts_root: [u8; 32],
+    pub logs_bloom: Vector<u8, BYTES_PER_LOGS_BLOOM>,
+    pub prev_randao: [u8; 32],
+    pub block_number: u64,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    pub timestamp: u64,
+    pub extra_data: List<u8, MAX_EXTRA_DATA_BYTES>,
+    pub base_fee_per_gas: [u8; 32],
+    pub block_hash: [u8; 32],
+    pub transactions_root: [u8; 32],
+    pub withdrawals_root: [u8; 32],
+    pub blob_gas_used: u64,
+    pub excess_blob_gas: u64,
+}
+// Electra doesn't change the execution payload header.
+pub type SSZExecutionPayloadHeaderElectra = SSZExecutionPayloadHeaderDeneb;
+
+#[derive(Default, SimpleSerialize, Clone, Debug)]
+pub struct SSZLightClientHeaderCapella {
+    pub beacon: SSZBeaconBlockHeader,
+    pub execution: SSZExecutionPayloadHeaderCapella,
+    pub execution_branch: Vector<[u8; 32], 4>,
+}
+#[derive(Default, SimpleSerialize, Clone, Debug)]
+pub struct SSZLightClientHeaderDeneb {
+    pub beacon: SSZBeaconBlockHeader,
+    pub execution: SSZExecutionPayloadHeaderDeneb,
+    pub execution_branch: Vector<[u8; 32], 4>,
+}
+pub type SSZLightClientHeaderElectra = SSZLightClientHeaderDeneb;
+
+#[derive(Default, SimpleSerialize)]
+pub struct SSZSyncCommitteePeriodUpdateCapella {
+    pub attested_header: SSZLightClientHeaderCapella,
+    pub next_sync_committee: SSZSyncCommittee,
+    pub next_sync_committee_branch: Vector<[u8; 32], 5>,
+    pub finalized_header: SSZLightClientHeaderCapella,
+    pub finality_branch: Vector<[u8; 32], 6>,
+    pub sync_aggregate: SSZSyncAggregate,
+    pub signature_slot: u64,
+}
+#[derive(Default, SimpleSerialize)]
+pub struct SSZSyncCommitteePeriodUpdateDeneb {
+    pub attested_header: SSZLightClientHeaderDeneb,
+    pub next_sync_committee: SSZSyncCommittee,
+    pub next_sync_committee_branch: Vector<[u8; 32], 5>,
+    pub finalized_header: SSZLightClientHeaderDeneb,
+    pub finality_branch: Vector<[u8; 32], 6>,
+    pub sync_aggregate: SSZSyncAggregate,
+    pub signature_slot: u64,
+}
+#[derive(Default, SimpleSerialize)]
+pub struct SSZSyncCommitteePeriodUpdateElectra {
+    pub attested_header: SSZLightClientHeaderElectra,
+    pub next_sync_committee: SSZSyncCommittee,
+    pub next_sync_committee_branch: Vector<[u8; 32], 6>,
+    pub finalized_header: SSZLightClientHeaderElectra,
+    pub finality_branch: Vector<[u8; 32], 7>,
+    pub sync_aggregate: SSZSyncAggregate,
+    pub signature_slot: u64,
+}
+#[derive(Default, SimpleSerialize)]
+pub struct SSZFinalizedHeaderUpdateCapella {
+    pub attested_header: SSZLightClientHeaderCapella,
+    pub finalized_header: SSZLightClientHeaderCapella,
+    pub finality_branch: Vector<[u8; 32], 6>,
+    pub sync_aggregate: SSZSyncAggregate,
+    pub signature_slot: u64,
+}
+#[derive(Default, SimpleSerialize)]
+pub struct SSZFinalizedHeaderUpdateDeneb {
+    pub attested_header: SSZLightClientHeaderDeneb,
+    pub finalized_header: SSZLightClientHeaderDeneb,
+    pub finality_branch: Vector<[u8; 32], 6>,
+    pub sync_aggregate: SSZSyncAggregate,
+    pub signature_slot: u64,
+}
+#[derive(Default, SimpleSerialize)]
+pub struct SSZFinalizedHeaderUpdateElectra {
+    pub attested_header: SSZLightClientHeaderElectra,
+    pub finalized_header: SSZLightClientHeaderElectra,
+    pub finality_branch: Vector<[u8; 32], 7>,
+    pub sync_aggregate: SSZSyncAggregate,
+    pub signature_slot: u64,
+}
+#[derive(Default, SimpleSerialize)]
+pub struct SSZLightClientOptimisticUpdateCapella {
+    pub attested_header: SSZLightClientHeaderCapella,
+    pub sync_aggregate: SSZSyncAggregate,
+    pub signature_slot: u64,
+}
+#[derive(Default, SimpleSerialize)]
+pub struct SSZLightClientOptimisticUpdateDeneb {
+    pub attested_header: SSZLightClientHeaderDeneb,
+    pub sync_aggregate: SSZSyncAggregate,
+    pub signature_slot: u64,
+}
+// The optimistic update carries no branches, so Electra didn't change its shape.
+pub type SSZLightClientOptimisticUpdateElectra = SSZLightClientOptimisticUpdateDeneb;
+#[derive(Default, SimpleSerialize)]
+pub struct SSZLightClientBootstrapCapella {
+    pub header: SSZLightClientHeaderCapella,
+    pub current_sync_committee: SSZSyncCommittee,
+    pub current_sync_committee_branch: Vector<[u8; 32], 5>,
+}
+#[derive(Default, SimpleSerialize)]
+pub struct SSZLightClientBootstrapDeneb {
+    pub header: SSZLightClientHeaderDeneb,
+    pub current_sync_committee: SSZSyncCommittee,
+    pub current_sync_committee_branch: Vector<[u8; 32], 5>,
+}
+#[derive(Default, SimpleSerialize)]
+pub struct SSZLightClientBootstrapElectra {
+    pub header: SSZLightClientHeaderElectra,
+    pub current_sync_committee: SSZSyncCommittee,
+    pub current_sync_committee_branch: Vector<[u8; 32], 6>,
+}
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
 pub struct SyncCommittee {
     // should this be a smallvec???
     pub pubkeys: Vec<PublicKey>,
     pub aggregate_pubkey: PublicKey,
 }
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[derive(Clone, Debug)]
 pub struct SyncCommitteePeriodUpdate {
-    pub attested_header: BeaconHeader,
+    pub attested_header: LightClientHeader,
     pub next_sync_committee: SyncCommittee,
     // was a bounded vec
     pub next_sync_committee_branch: Vec<H256>,
-    pub finalized_header: BeaconHeader,
+    pub finalized_header: LightClientHeader,
     // was a bounded vec
     pub finality_branch: Vec<H256>,
     pub sync_aggregate: SyncAggregate,
-    // #[cfg_attr(feature = "std", serde(deserialize_with = "from_hex_to_fork_version"))]
-    pub fork_version: ForkVersion,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::beacon_api::from_quoted_u64"))]
+    pub signature_slot: u64,
+}
+
+impl SyncCommitteePeriodUpdate {
+    /// Decodes an update using the container of the fork its attested header belongs to.
+    pub fn decode<H: Hasher>(bytes: &[u8], spec: &ChainSpec<H>) -> Result<Self, LightClientError> {
+        let fork = fork_of_encoded_header(bytes, HeaderContainer::Update, spec)?;
+        Self::decode_in_fork(bytes, fork, spec)
+    }
+
+    /// Decodes an update using the container of `fork`, which is later than the fork of the
+    /// attested header when the update is served under the fork digest of a later fork. The
+    /// branches are cut back to the depth of the attested header's state.
+    pub fn decode_in_fork<H: Hasher>(
+        bytes: &[u8],
+        fork: ForkName,
+        spec: &ChainSpec<H>,
+    ) -> Result<Self, LightClientError> {
+        let mut update = match fork {
+            ForkName::Phase0 | ForkName::Altair | ForkName::Bellatrix => Self::try_from(bytes),
+            ForkName::Capella => {
+                decode_ssz::<SSZSyncCommitteePeriodUpdateCapella>(bytes, "update").map(Self::from)
+            }
+            ForkName::Deneb => {
+                decode_ssz::<SSZSyncCommitteePeriodUpdateDeneb>(bytes, "update").map(Self::from)
+            }
+            ForkName::Electra => {
+                decode_ssz::<SSZSyncCommitteePeriodUpdateElectra>(bytes, "update").map(Self::from)
+            }
+        }?;
+        let slot = update.attested_header.beacon.slot;
+        denormalize_branch(
+            &mut update.next_sync_committee_branch,
+            next_sync_committee_gindex(spec, slot),
+            MerkleBranch::NextSyncCommittee,
+        )?;
+        denormalize_branch(
+            &mut update.finality_branch,
+            finalized_root_gindex(spec, slot),
+            MerkleBranch::Finality,
+        )?;
+        Ok(update)
+    }
 }
 
 impl TryFrom<&[u8]> for SyncCommitteePeriodUpdate {
-    type Error = String;
-    fn try_from(bytes: &[u8]) -> Result<Self, String> {
+    type Error = LightClientError;
+    fn try_from(bytes: &[u8]) -> Result<Self, LightClientError> {
         let ssz_form: SSZSyncCommitteePeriodUpdate =
-            deserialize(&bytes).map_err(|_e| "Failed to decode previous update".to_string())?;
+            deserialize(&bytes).map_err(|_e| LightClientError::Decode { field: "update" })?;
         Ok(Self::from(ssz_form))
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct LightClientBootstrap {
+    pub header: LightClientHeader,
+    pub current_sync_committee: SyncCommittee,
+    pub current_sync_committee_branch: Vec<H256>,
+}
+
+impl LightClientBootstrap {
+    /// Decodes a bootstrap using the container of the fork its header belongs to.
+    pub fn decode<H: Hasher>(bytes: &[u8], spec: &ChainSpec<H>) -> Result<Self, LightClientError> {
+        let fork = fork_of_encoded_header(bytes, HeaderContainer::Bootstrap, spec)?;
+        Self::decode_in_fork(bytes, fork, spec)
+    }
+
+    /// Decodes a bootstrap using the container of `fork`, see
+    /// [`SyncCommitteePeriodUpdate::decode_in_fork`].
+    pub fn decode_in_fork<H: Hasher>(
+        bytes: &[u8],
+        fork: ForkName,
+        spec: &ChainSpec<H>,
+    ) -> Result<Self, LightClientError> {
+        let mut bootstrap = match fork {
+            ForkName::Phase0 | ForkName::Altair | ForkName::Bellatrix => Self::try_from(bytes),
+            ForkName::Capella => {
+                decode_ssz::<SSZLightClientBootstrapCapella>(bytes, "bootstrap").map(Self::from)
+            }
+            ForkName::Deneb => {
+                decode_ssz::<SSZLightClientBootstrapDeneb>(bytes, "bootstrap").map(Self::from)
+            }
+            ForkName::Electra => {
+                decode_ssz::<SSZLightClientBootstrapElectra>(bytes, "bootstrap").map(Self::from)
+            }
+        }?;
+        denormalize_branch(
+            &mut bootstrap.current_sync_committee_branch,
+            current_sync_committee_gindex(spec, bootstrap.header.beacon.slot),
+            MerkleBranch::CurrentSyncCommittee,
+        )?;
+        Ok(bootstrap)
+    }
+}
+
+impl TryFrom<&[u8]> for LightClientBootstrap {
+    type Error = LightClientError;
+    fn try_from(bytes: &[u8]) -> Result<Self, LightClientError> {
+        let ssz_form: SSZLightClientBootstrap =
+            deserialize(&bytes).map_err(|_e| LightClientError::Decode { field: "bootstrap" })?;
+        Ok(Self::from(ssz_form))
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 pub struct FinalizedHeaderUpdate  {
-	pub attested_header: BeaconHeader,
-	pub finalized_header: BeaconHeader,
+	pub attested_header: LightClientHeader,
+	pub finalized_header: LightClientHeader,
 	pub finality_branch: Vec<H256>,
 	pub sync_aggregate: SyncAggregate,
+	#[cfg_attr(feature = "serde", serde(deserialize_with = "crate::beacon_api::from_quoted_u64"))]
+	pub signature_slot: u64,
+}
+
+impl FinalizedHeaderUpdate {
+    /// Decodes an update using the container of the fork its attested header belongs to.
+    pub fn decode<H: Hasher>(bytes: &[u8], spec: &ChainSpec<H>) -> Result<Self, LightClientError> {
+        let fork = fork_of_encoded_header(bytes, HeaderContainer::FinalityUpdate, spec)?;
+        Self::decode_in_fork(bytes, fork, spec)
+    }
+
+    /// Decodes an update using the container of `fork`, see
+    /// [`SyncCommitteePeriodUpdate::decode_in_fork`].
+    pub fn decode_in_fork<H: Hasher>(
+        bytes: &[u8],
+        fork: ForkName,
+        spec: &ChainSpec<H>,
+    ) -> Result<Self, LightClientError> {
+        let mut update = match fork {
+            ForkName::Phase0 | ForkName::Altair | ForkName::Bellatrix => {
+                decode_ssz::<SSZFinalizedHeaderUpdate>(bytes, "update").map(Self::from)
+            }
+            ForkName::Capella => {
+                decode_ssz::<SSZFinalizedHeaderUpdateCapella>(bytes, "update").map(Self::from)
+            }
+            ForkName::Deneb => {
+                decode_ssz::<SSZFinalizedHeaderUpdateDeneb>(bytes, "update").map(Self::from)
+            }
+            ForkName::Electra => {
+                decode_ssz::<SSZFinalizedHeaderUpdateElectra>(bytes, "update").map(Self::from)
+            }
+        }?;
+        denormalize_branch(
+            &mut update.finality_branch,
+            finalized_root_gindex(spec, update.attested_header.beacon.slot),
+            MerkleBranch::Finality,
+        )?;
+        Ok(update)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct LightClientOptimisticUpdate {
+    pub attested_header: LightClientHeader,
+    pub sync_aggregate: SyncAggregate,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::beacon_api::from_quoted_u64"))]
+    pub signature_slot: u64,
+}
+
+impl LightClientOptimisticUpdate {
+    /// Decodes an update using the container of the fork its attested header belongs to.
+    pub fn decode<H: Hasher>(bytes: &[u8], spec: &ChainSpec<H>) -> Result<Self, LightClientError> {
+        let fork = fork_of_encoded_header(bytes, HeaderContainer::OptimisticUpdate, spec)?;
+        Self::decode_in_fork(bytes, fork)
+    }
+
+    /// Decodes an update using the container of `fork`, see
+    /// [`SyncCommitteePeriodUpdate::decode_in_fork`]. It carries no branches to cut back.
+    pub fn decode_in_fork(bytes: &[u8], fork: ForkName) -> Result<Self, LightClientError> {
+        match fork {
+            ForkName::Phase0 | ForkName::Altair | ForkName::Bellatrix => {
+                decode_ssz::<SSZLightClientOptimisticUpdate>(bytes, "update").map(Self::from)
+            }
+            ForkName::Capella => {
+                decode_ssz::<SSZLightClientOptimisticUpdateCapella>(bytes, "update")
+                    .map(Self::from)
+            }
+            ForkName::Deneb => {
+                decode_ssz::<SSZLightClientOptimisticUpdateDeneb>(bytes, "update").map(Self::from)
+            }
+            ForkName::Electra => {
+                decode_ssz::<SSZLightClientOptimisticUpdateElectra>(bytes, "update")
+                    .map(Self::from)
+            }
+        }
+    }
 }
 
+pub(crate) const BEACON_HEADER_SIZE: usize = 112;
+pub(crate) const OFFSET_SIZE: usize = 4;
+pub(crate) const SYNC_COMMITTEE_BITS_SIZE: usize = SYNC_COMMITTEE_SIZE / 8;
 
+/// The light client containers that start with a header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HeaderContainer {
+    Update,
+    FinalityUpdate,
+    OptimisticUpdate,
+    Bootstrap,
+}
+
+impl HeaderContainer {
+    /// Size of the fixed part of the container in `fork`. Before Capella that is the whole
+    /// container, later every header is replaced by its offset.
+    pub fn fixed_size(&self, fork: ForkName) -> usize {
+        let header = if fork < ForkName::Capella {
+            BEACON_HEADER_SIZE
+        } else {
+            OFFSET_SIZE
+        };
+        let electra = fork >= ForkName::Electra;
+        let depth = |depth: u64, depth_electra: u64| (if electra { depth_electra } else { depth }) as usize;
+        let sync_committee = (SYNC_COMMITTEE_SIZE + 1) * PUBKEY_SIZE;
+        let sync_aggregate = SYNC_COMMITTEE_BITS_SIZE + SIGNATURE_SIZE;
+        let finality_branch = depth(FINALIZED_ROOT_DEPTH, FINALIZED_ROOT_DEPTH_ELECTRA) * 32;
+        match self {
+            HeaderContainer::Update => {
+                let branch = depth(NEXT_SYNC_COMMITTEE_DEPTH, NEXT_SYNC_COMMITTEE_DEPTH_ELECTRA);
+                header + sync_committee + branch * 32 + header + finality_branch
+                    + sync_aggregate + 8
+            }
+            HeaderContainer::FinalityUpdate => {
+                2 * header + finality_branch + sync_aggregate + 8
+            }
+            HeaderContainer::OptimisticUpdate => header + sync_aggregate + 8,
+            HeaderContainer::Bootstrap => {
+                let branch =
+                    depth(CURRENT_SYNC_COMMITTEE_DEPTH, CURRENT_SYNC_COMMITTEE_DEPTH_ELECTRA);
+                header + sync_committee + branch * 32
+            }
+        }
+    }
+}
+
+/// Works out which fork's `container` `bytes` are encoded with.
+///
+/// Every update and bootstrap container starts with a header. Before Capella that is the fixed
+/// size beacon header, so the container starts with its slot. From Capella on the header is
+/// variable size and the container starts with the offset of the header instead. Either reading
+/// fits any bytes, so the Capella one is only taken when the offset points right behind the fixed
+/// part of the container of the fork it leads to, and the earlier one when the bytes are exactly
+/// as long as the container of that fork.
+pub fn fork_of_encoded_header<H: Hasher>(
+    bytes: &[u8],
+    container: HeaderContainer,
+    spec: &ChainSpec<H>,
+) -> Result<ForkName, LightClientError> {
+    let offset = u32::from_le_bytes(read_bytes(bytes, 0)?) as usize;
+    if let Ok(slot) = read_u64(bytes, offset) {
+        let fork = spec.fork_at_slot(slot);
+        if fork >= ForkName::Capella && offset == container.fixed_size(fork) {
+            return Ok(fork);
+        }
+    }
+    let fork = spec.fork_at_slot(read_u64(bytes, 0)?);
+    if fork >= ForkName::Capella || bytes.len() != container.fixed_size(fork) {
+        return Err(LightClientError::Decode { field: "header" });
+    }
+    Ok(fork)
+}
+
+pub(crate) fn read_u64(bytes: &[u8], offset: usize) -> Result<u64, LightClientError> {
+    Ok(u64::from_le_bytes(read_bytes(bytes, offset)?))
+}
+
+pub(crate) fn read_bytes<const N: usize>(bytes: &[u8], offset: usize) -> Result<[u8; N], LightClientError> {
+    offset
+        .checked_add(N)
+        .and_then(|end| bytes.get(offset..end))
+        .and_then(|b| b.try_into().ok())
+        .ok_or(LightClientError::Decode { field: "header" })
+}
+
+/// Undoes `normalize_merkle_branch` of the Electra light client specs. The containers from Electra
+/// on pad the branches into older states with zero nodes in front, up to the Electra depth.
+fn denormalize_branch(
+    branch: &mut Vec<H256>,
+    gindex: GeneralizedIndex,
+    kind: MerkleBranch,
+) -> Result<(), LightClientError> {
+    let extra = branch.len().saturating_sub(get_generalized_index_length(gindex) as usize);
+    if branch[..extra].iter().any(|node| *node != H256::default()) {
+        return Err(LightClientError::InvalidMerkleBranch(kind));
+    }
+    branch.drain(..extra);
+    Ok(())
+}
+
+pub(crate) fn decode_ssz<T: SimpleSerializeTrait>(bytes: &[u8], field: &'static str) -> Result<T, LightClientError> {
+    deserialize(bytes).map_err(|_e| LightClientError::Decode { field })
+}
 
 impl From<SSZFinalizedHeaderUpdate> for FinalizedHeaderUpdate {
     fn from(ssz: SSZFinalizedHeaderUpdate) -> Self {
@@ -208,6 +722,43 @@ impl From<SSZFinalizedHeaderUpdate> for FinalizedHeaderUpdate {
             finalized_header:ssz.finalized_header.into(),
             finality_branch: ssz.finality_branch.iter().map(|v| H256(*v)).collect(),
             sync_aggregate: ssz.sync_aggregate.into(),
+            signature_slot: ssz.signature_slot,
+        }
+    }
+}
+
+impl From<SSZFinalizedHeaderUpdateCapella> for FinalizedHeaderUpdate {
+    fn from(ssz: SSZFinalizedHeaderUpdateCapella) -> Self {
+        FinalizedHeaderUpdate {
+            attested_header: ssz.attested_header.into(),
+            finalized_header: ssz.finalized_header.into(),
+            finality_branch: ssz.finality_branch.iter().map(|v| H256(*v)).collect(),
+            sync_aggregate: ssz.sync_aggregate.into(),
+            signature_slot: ssz.signature_slot,
+        }
+    }
+}
+
+impl From<SSZFinalizedHeaderUpdateDeneb> for FinalizedHeaderUpdate {
+    fn from(ssz: SSZFinalizedHeaderUpdateDeneb) -> Self {
+        FinalizedHeaderUpdate {
+            attested_header: ssz.attested_header.into(),
+            finalized_header: ssz.finalized_header.into(),
+            finality_branch: ssz.finality_branch.iter().map(|v| H256(*v)).collect(),
+            sync_aggregate: ssz.sync_aggregate.into(),
+            signature_slot: ssz.signature_slot,
+        }
+    }
+}
+
+impl From<SSZFinalizedHeaderUpdateElectra> for FinalizedHeaderUpdate {
+    fn from(ssz: SSZFinalizedHeaderUpdateElectra) -> Self {
+        FinalizedHeaderUpdate {
+            attested_header: ssz.attested_header.into(),
+            finalized_header: ssz.finalized_header.into(),
+            finality_branch: ssz.finality_branch.iter().map(|v| H256(*v)).collect(),
+            sync_aggregate: ssz.sync_aggregate.into(),
+            signature_slot: ssz.signature_slot,
         }
     }
 }
@@ -226,7 +777,147 @@ impl From<SSZSyncCommitteePeriodUpdate> for SyncCommitteePeriodUpdate {
             finalized_header: value.finalized_header.into(),
             finality_branch: value.finality_branch.iter().map(|v| H256(*v)).collect(),
             sync_aggregate: value.sync_aggregate.into(),
-            fork_version: value.fork_version,
+            signature_slot: value.signature_slot,
+        }
+    }
+}
+
+impl From<SSZSyncCommitteePeriodUpdateCapella> for SyncCommitteePeriodUpdate {
+    fn from(value: SSZSyncCommitteePeriodUpdateCapella) -> Self {
+        SyncCommitteePeriodUpdate {
+            attested_header: value.attested_header.into(),
+            next_sync_committee: value.next_sync_committee.into(),
+            next_sync_committee_branch: value
+                .next_sync_committee_branch
+                .iter()
+                .map(|v| H256(*v))
+                .collect(),
+            finalized_header: value.finalized_header.into(),
+            finality_branch: value.finality_branch.iter().map(|v| H256(*v)).collect(),
+            sync_aggregate: value.sync_aggregate.into(),
+            signature_slot: value.signature_slot,
+        }
+    }
+}
+
+impl From<SSZSyncCommitteePeriodUpdateDeneb> for SyncCommitteePeriodUpdate {
+    fn from(value: SSZSyncCommitteePeriodUpdateDeneb) -> Self {
+        SyncCommitteePeriodUpdate {
+            attested_header: value.attested_header.into(),
+            next_sync_committee: value.next_sync_committee.into(),
+            next_sync_committee_branch: value
+                .next_sync_committee_branch
+                .iter()
+                .map(|v| H256(*v))
+                .collect(),
+            finalized_header: value.finalized_header.into(),
+            finality_branch: value.finality_branch.iter().map(|v| H256(*v)).collect(),
+            sync_aggregate: value.sync_aggregate.into(),
+            signature_slot: value.signature_slot,
+        }
+    }
+}
+
+impl From<SSZSyncCommitteePeriodUpdateElectra> for SyncCommitteePeriodUpdate {
+    fn from(value: SSZSyncCommitteePeriodUpdateElectra) -> Self {
+        SyncCommitteePeriodUpdate {
+            attested_header: value.attested_header.into(),
+            next_sync_committee: value.next_sync_committee.into(),
+            next_sync_committee_branch: value
+                .next_sync_committee_branch
+                .iter()
+                .map(|v| H256(*v))
+                .collect(),
+            finalized_header: value.finalized_header.into(),
+            finality_branch: value.finality_branch.iter().map(|v| H256(*v)).collect(),
+            sync_aggregate: value.sync_aggregate.into(),
+            signature_slot: value.signature_slot,
+        }
+    }
+}
+
+impl From<SSZLightClientOptimisticUpdate> for LightClientOptimisticUpdate {
+    fn from(ssz: SSZLightClientOptimisticUpdate) -> Self {
+        LightClientOptimisticUpdate {
+            attested_header: ssz.attested_header.into(),
+            sync_aggregate: ssz.sync_aggregate.into(),
+            signature_slot: ssz.signature_slot,
+        }
+    }
+}
+
+impl From<SSZLightClientOptimisticUpdateCapella> for LightClientOptimisticUpdate {
+    fn from(ssz: SSZLightClientOptimisticUpdateCapella) -> Self {
+        LightClientOptimisticUpdate {
+            attested_header: ssz.attested_header.into(),
+            sync_aggregate: ssz.sync_aggregate.into(),
+            signature_slot: ssz.signature_slot,
+        }
+    }
+}
+
+impl From<SSZLightClientOptimisticUpdateDeneb> for LightClientOptimisticUpdate {
+    fn from(ssz: SSZLightClientOptimisticUpdateDeneb) -> Self {
+        LightClientOptimisticUpdate {
+            attested_header: ssz.attested_header.into(),
+            sync_aggregate: ssz.sync_aggregate.into(),
+            signature_slot: ssz.signature_slot,
+        }
+    }
+}
+
+impl From<SSZLightClientBootstrap> for LightClientBootstrap {
+    fn from(value: SSZLightClientBootstrap) -> Self {
+        LightClientBootstrap {
+            header: value.header.into(),
+            current_sync_committee: value.current_sync_committee.into(),
+            current_sync_committee_branch: value
+                .current_sync_committee_branch
+                .iter()
+                .map(|v| H256(*v))
+                .collect(),
+        }
+    }
+}
+
+impl From<SSZLightClientBootstrapCapella> for LightClientBootstrap {
+    fn from(value: SSZLightClientBootstrapCapella) -> Self {
+        LightClientBootstrap {
+            header: value.header.into(),
+            current_sync_committee: value.current_sync_committee.into(),
+            current_sync_committee_branch: value
+                .current_sync_committee_branch
+                .iter()
+                .map(|v| H256(*v))
+                .collect(),
+        }
+    }
+}
+
+impl From<SSZLightClientBootstrapDeneb> for LightClientBootstrap {
+    fn from(value: SSZLightClientBootstrapDeneb) -> Self {
+        LightClientBootstrap {
+            header: value.header.into(),
+            current_sync_committee: value.current_sync_committee.into(),
+            current_sync_committee_branch: value
+                .current_sync_committee_branch
+                .iter()
+                .map(|v| H256(*v))
+                .collect(),
+        }
+    }
+}
+
+impl From<SSZLightClientBootstrapElectra> for LightClientBootstrap {
+    fn from(value: SSZLightClientBootstrapElectra) -> Self {
+        LightClientBootstrap {
+            header: value.header.into(),
+            current_sync_committee: value.current_sync_committee.into(),
+            current_sync_committee_branch: value
+                .current_sync_committee_branch
+                .iter()
+                .map(|v| H256(*v))
+                .collect(),
         }
     }
 }
@@ -244,6 +935,84 @@ impl From<SSZBeaconBlockHeader> for BeaconHeader {
     }
 }
 
+impl From<SSZBeaconBlockHeader> for LightClientHeader {
+    fn from(value: SSZBeaconBlockHeader) -> Self {
+        LightClientHeader {
+            beacon: value.into(),
+            execution: None,
+            execution_branch: Vec::new(),
+        }
+    }
+}
+
+impl From<SSZLightClientHeaderCapella> for LightClientHeader {
+    fn from(value: SSZLightClientHeaderCapella) -> Self {
+        LightClientHeader {
+            beacon: value.beacon.into(),
+            execution: Some(value.execution.into()),
+            execution_branch: value.execution_branch.iter().map(|v| H256(*v)).collect(),
+        }
+    }
+}
+
+impl From<SSZLightClientHeaderDeneb> for LightClientHeader {
+    fn from(value: SSZLightClientHeaderDeneb) -> Self {
+        LightClientHeader {
+            beacon: value.beacon.into(),
+            execution: Some(value.execution.into()),
+            execution_branch: value.execution_branch.iter().map(|v| H256(*v)).collect(),
+        }
+    }
+}
+
+impl From<SSZExecutionPayloadHeaderCapella> for ExecutionPayloadHeader {
+    fn from(value: SSZExecutionPayloadHeaderCapella) -> Self {
+        ExecutionPayloadHeader {
+            parent_hash: value.parent_hash.into(),
+            fee_recipient: value.fee_recipient[..20].try_into().unwrap(),
+            state_root: value.state_root.into(),
+            receipts_root: value.receipts_root.into(),
+            logs_bloom: value.logs_bloom.to_vec(),
+            prev_randao: value.prev_randao.into(),
+            block_number: value.block_number,
+            gas_limit: value.gas_limit,
+            gas_used: value.gas_used,
+            timestamp: value.timestamp,
+            extra_data: value.extra_data.to_vec(),
+            base_fee_per_gas: value.base_fee_per_gas,
+            block_hash: value.block_hash.into(),
+            transactions_root: value.transactions_root.into(),
+            withdrawals_root: value.withdrawals_root.into(),
+            blob_gas_used: 0,
+            excess_blob_gas: 0,
+        }
+    }
+}
+
+impl From<SSZExecutionPayloadHeaderDeneb> for ExecutionPayloadHeader {
+    fn from(value: SSZExecutionPayloadHeaderDeneb) -> Self {
+        ExecutionPayloadHeader {
+            parent_hash: value.parent_hash.into(),
+            fee_recipient: value.fee_recipient[..20].try_into().unwrap(),
+            state_root: value.state_root.into(),
+            receipts_root: value.receipts_root.into(),
+            logs_bloom: value.logs_bloom.to_vec(),
+            prev_randao: value.prev_randao.into(),
+            block_number: value.block_number,
+            gas_limit: value.gas_limit,
+            gas_used: value.gas_used,
+            timestamp: value.timestamp,
+            extra_data: value.extra_data.to_vec(),
+            base_fee_per_gas: value.base_fee_per_gas,
+            block_hash: value.block_hash.into(),
+            transactions_root: value.transactions_root.into(),
+            withdrawals_root: value.withdrawals_root.into(),
+            blob_gas_used: value.blob_gas_used,
+            excess_blob_gas: value.excess_blob_gas,
+        }
+    }
+}
+
 impl From<SSZSyncCommittee> for SyncCommittee {
     fn from(value: SSZSyncCommittee) -> Self {
         tryprintln!("from ssz sync committee");
@@ -266,4 +1035,567 @@ impl From<SSZSyncAggregate> for SyncAggregate {
             sync_committee_signature: value.sync_committee_signature.to_vec(),
         }
     }
-}
\ No newline at end of file
+}
+impl BeaconHeader {
+    /// The block root of the header.
+    pub fn hash_tree_root(&self) -> Root {
+        self.hash_tree_root_with::<DefaultHasher>()
+    }
+
+    /// The block root of the header, hashed with `H`.
+    pub fn hash_tree_root_with<H: Hasher>(&self) -> Root {
+        H256(hash_tree_root_beacon_header::<H>(self))
+    }
+
+    pub fn to_ssz_bytes(&self) -> Result<Vec<u8>, LightClientError> {
+        encode_ssz(&SSZBeaconBlockHeader::from(self), "header")
+    }
+}
+
+impl ExecutionPayloadHeader {
+    /// Whether this is the all-zero payload the containers from Capella on carry for headers that
+    /// have none. Decoded ones come with a zero logs bloom, built ones may leave it empty.
+    pub fn is_empty(&self) -> bool {
+        let ExecutionPayloadHeader {
+            parent_hash,
+            fee_recipient,
+            state_root,
+            receipts_root,
+            logs_bloom,
+            prev_randao,
+            block_number,
+            gas_limit,
+            gas_used,
+            timestamp,
+            extra_data,
+            base_fee_per_gas,
+            block_hash,
+            transactions_root,
+            withdrawals_root,
+            blob_gas_used,
+            excess_blob_gas,
+        } = self;
+        let roots = [
+            parent_hash,
+            state_root,
+            receipts_root,
+            prev_randao,
+            block_hash,
+            transactions_root,
+            withdrawals_root,
+        ];
+        roots.iter().all(|root| **root == H256::default())
+            && *fee_recipient == [0u8; 20]
+            && logs_bloom.iter().all(|byte| *byte == 0)
+            && [block_number, gas_limit, gas_used, timestamp, blob_gas_used, excess_blob_gas]
+                .iter()
+                .all(|value| **value == 0)
+            && extra_data.is_empty()
+            && *base_fee_per_gas == [0u8; 32]
+    }
+}
+
+impl LightClientHeader {
+    /// The execution payload header, after checking it against the body root of the beacon
+    /// header. Fails for headers from before Capella, which have none, even when a newer
+    /// container carries an empty one for them.
+    pub fn verified_execution<H: Hasher>(
+        &self,
+        spec: &ChainSpec<H>,
+    ) -> Result<&ExecutionPayloadHeader, LightClientError> {
+        if spec.fork_at_slot(self.beacon.slot) < ForkName::Capella {
+            return Err(LightClientError::InvalidLightClientHeader);
+        }
+        verify_light_client_header(self, spec)?;
+        self.execution
+            .as_ref()
+            .ok_or(LightClientError::InvalidLightClientHeader)
+    }
+
+    /// Encodes the header with the container of the fork its slot belongs to.
+    pub fn to_ssz_bytes<H: Hasher>(
+        &self,
+        spec: &ChainSpec<H>,
+    ) -> Result<Vec<u8>, LightClientError> {
+        match spec.fork_at_slot(self.beacon.slot) {
+            ForkName::Phase0 | ForkName::Altair | ForkName::Bellatrix => {
+                encode_ssz(&SSZBeaconBlockHeader::try_from(self)?, "header")
+            }
+            ForkName::Capella => encode_ssz(&SSZLightClientHeaderCapella::try_from(self)?, "header"),
+            ForkName::Deneb | ForkName::Electra => {
+                encode_ssz(&SSZLightClientHeaderDeneb::try_from(self)?, "header")
+            }
+        }
+    }
+}
+
+impl SyncCommittee {
+    /// Fails if the committee doesn't have exactly `SYNC_COMMITTEE_SIZE` members.
+    pub fn hash_tree_root(&self) -> Result<Root, LightClientError> {
+        self.hash_tree_root_with::<DefaultHasher>()
+    }
+
+    /// Same as [`SyncCommittee::hash_tree_root`], hashed with `H`.
+    pub fn hash_tree_root_with<H: Hasher>(&self) -> Result<Root, LightClientError> {
+        hash_tree_root_sync_committee::<H>(self).map(H256)
+    }
+
+    pub fn to_ssz_bytes(&self) -> Result<Vec<u8>, LightClientError> {
+        encode_ssz(&SSZSyncCommittee::try_from(self)?, "sync_committee")
+    }
+}
+
+impl SyncCommitteePeriodUpdate {
+    /// The block root of the finalized header, as submitted to the challenge contract.
+    pub fn finalized_block_root(&self) -> Root {
+        self.finalized_header.beacon.hash_tree_root()
+    }
+
+    /// Encodes the update with the container of the fork its attested header belongs to, the
+    /// inverse of [`SyncCommitteePeriodUpdate::decode`].
+    pub fn to_ssz_bytes<H: Hasher>(
+        &self,
+        spec: &ChainSpec<H>,
+    ) -> Result<Vec<u8>, LightClientError> {
+        match spec.fork_at_slot(self.attested_header.beacon.slot) {
+            ForkName::Phase0 | ForkName::Altair | ForkName::Bellatrix => {
+                encode_ssz(&SSZSyncCommitteePeriodUpdate::try_from(self)?, "update")
+            }
+            ForkName::Capella => {
+                encode_ssz(&SSZSyncCommitteePeriodUpdateCapella::try_from(self)?, "update")
+            }
+            ForkName::Deneb => {
+                encode_ssz(&SSZSyncCommitteePeriodUpdateDeneb::try_from(self)?, "update")
+            }
+            ForkName::Electra => {
+                encode_ssz(&SSZSyncCommitteePeriodUpdateElectra::try_from(self)?, "update")
+            }
+        }
+    }
+}
+
+impl FinalizedHeaderUpdate {
+    /// The block root of the finalized header.
+    pub fn finalized_block_root(&self) -> Root {
+        self.finalized_header.beacon.hash_tree_root()
+    }
+
+    /// Encodes the update with the container of the fork its attested header belongs to, the
+    /// inverse of [`FinalizedHeaderUpdate::decode`].
+    pub fn to_ssz_bytes<H: Hasher>(
+        &self,
+        spec: &ChainSpec<H>,
+    ) -> Result<Vec<u8>, LightClientError> {
+        match spec.fork_at_slot(self.attested_header.beacon.slot) {
+            ForkName::Phase0 | ForkName::Altair | ForkName::Bellatrix => {
+                encode_ssz(&SSZFinalizedHeaderUpdate::try_from(self)?, "update")
+            }
+            ForkName::Capella => {
+                encode_ssz(&SSZFinalizedHeaderUpdateCapella::try_from(self)?, "update")
+            }
+            ForkName::Deneb => encode_ssz(&SSZFinalizedHeaderUpdateDeneb::try_from(self)?, "update"),
+            ForkName::Electra => {
+                encode_ssz(&SSZFinalizedHeaderUpdateElectra::try_from(self)?, "update")
+            }
+        }
+    }
+}
+
+impl LightClientOptimisticUpdate {
+    /// Encodes the update with the container of the fork its attested header belongs to, the
+    /// inverse of [`LightClientOptimisticUpdate::decode`].
+    pub fn to_ssz_bytes<H: Hasher>(
+        &self,
+        spec: &ChainSpec<H>,
+    ) -> Result<Vec<u8>, LightClientError> {
+        match spec.fork_at_slot(self.attested_header.beacon.slot) {
+            ForkName::Phase0 | ForkName::Altair | ForkName::Bellatrix => {
+                encode_ssz(&SSZLightClientOptimisticUpdate::try_from(self)?, "update")
+            }
+            ForkName::Capella => {
+                encode_ssz(&SSZLightClientOptimisticUpdateCapella::try_from(self)?, "update")
+            }
+            ForkName::Deneb | ForkName::Electra => {
+                encode_ssz(&SSZLightClientOptimisticUpdateDeneb::try_from(self)?, "update")
+            }
+        }
+    }
+}
+
+impl LightClientBootstrap {
+    /// Encodes the bootstrap with the container of the fork its header belongs to, the inverse
+    /// of [`LightClientBootstrap::decode`].
+    pub fn to_ssz_bytes<H: Hasher>(
+        &self,
+        spec: &ChainSpec<H>,
+    ) -> Result<Vec<u8>, LightClientError> {
+        match spec.fork_at_slot(self.header.beacon.slot) {
+            ForkName::Phase0 | ForkName::Altair | ForkName::Bellatrix => {
+                encode_ssz(&SSZLightClientBootstrap::try_from(self)?, "bootstrap")
+            }
+            ForkName::Capella => {
+                encode_ssz(&SSZLightClientBootstrapCapella::try_from(self)?, "bootstrap")
+            }
+            ForkName::Deneb => encode_ssz(&SSZLightClientBootstrapDeneb::try_from(self)?, "bootstrap"),
+            ForkName::Electra => {
+                encode_ssz(&SSZLightClientBootstrapElectra::try_from(self)?, "bootstrap")
+            }
+        }
+    }
+}
+
+impl From<&BeaconHeader> for SSZBeaconBlockHeader {
+    fn from(value: &BeaconHeader) -> Self {
+        SSZBeaconBlockHeader {
+            slot: value.slot,
+            proposer_index: value.proposer_index,
+            parent_root: value.parent_root.0,
+            state_root: value.state_root.0,
+            body_root: value.body_root.0,
+        }
+    }
+}
+
+// Only headers without an execution payload fit the containers from before Capella.
+impl TryFrom<&LightClientHeader> for SSZBeaconBlockHeader {
+    type Error = LightClientError;
+    fn try_from(value: &LightClientHeader) -> Result<Self, LightClientError> {
+        let empty_execution = value.execution.as_ref().map_or(true, ExecutionPayloadHeader::is_empty);
+        if !empty_execution || value.execution_branch.iter().any(|node| *node != H256::default()) {
+            return Err(LightClientError::InvalidLightClientHeader);
+        }
+        Ok((&value.beacon).into())
+    }
+}
+
+impl TryFrom<&LightClientHeader> for SSZLightClientHeaderCapella {
+    type Error = LightClientError;
+    fn try_from(value: &LightClientHeader) -> Result<Self, LightClientError> {
+        Ok(SSZLightClientHeaderCapella {
+            beacon: (&value.beacon).into(),
+            execution: match &value.execution {
+                Some(execution) if !execution.is_empty() => execution.try_into()?,
+                _ => SSZExecutionPayloadHeaderCapella::default(),
+            },
+            execution_branch: ssz_execution_branch(&value.execution_branch)?,
+        })
+    }
+}
+
+impl TryFrom<&LightClientHeader> for SSZLightClientHeaderDeneb {
+    type Error = LightClientError;
+    fn try_from(value: &LightClientHeader) -> Result<Self, LightClientError> {
+        Ok(SSZLightClientHeaderDeneb {
+            beacon: (&value.beacon).into(),
+            execution: match &value.execution {
+                Some(execution) if !execution.is_empty() => execution.try_into()?,
+                _ => SSZExecutionPayloadHeaderDeneb::default(),
+            },
+            execution_branch: ssz_execution_branch(&value.execution_branch)?,
+        })
+    }
+}
+
+impl TryFrom<&ExecutionPayloadHeader> for SSZExecutionPayloadHeaderCapella {
+    type Error = LightClientError;
+    fn try_from(value: &ExecutionPayloadHeader) -> Result<Self, LightClientError> {
+        // The Capella container has no room for the blob gas fields.
+        if value.blob_gas_used != 0 || value.excess_blob_gas != 0 {
+            return Err(LightClientError::InvalidLightClientHeader);
+        }
+        Ok(SSZExecutionPayloadHeaderCapella {
+            parent_hash: value.parent_hash.0,
+            fee_recipient: ssz_vector(value.fee_recipient.to_vec())?,
+            state_root: value.state_root.0,
+            receipts_root: value.receipts_root.0,
+            logs_bloom: ssz_vector(value.logs_bloom.clone())?,
+            prev_randao: value.prev_randao.0,
+            block_number: value.block_number,
+            gas_limit: value.gas_limit,
+            gas_used: value.gas_used,
+            timestamp: value.timestamp,
+            extra_data: ssz_list(value.extra_data.clone())?,
+            base_fee_per_gas: value.base_fee_per_gas,
+            block_hash: value.block_hash.0,
+            transactions_root: value.transactions_root.0,
+            withdrawals_root: value.withdrawals_root.0,
+        })
+    }
+}
+
+impl TryFrom<&ExecutionPayloadHeader> for SSZExecutionPayloadHeaderDeneb {
+    type Error = LightClientError;
+    fn try_from(value: &ExecutionPayloadHeader) -> Result<Self, LightClientError> {
+        Ok(SSZExecutionPayloadHeaderDeneb {
+            parent_hash: value.parent_hash.0,
+            fee_recipient: ssz_vector(value.fee_recipient.to_vec())?,
+            state_root: value.state_root.0,
+            receipts_root: value.receipts_root.0,
+            logs_bloom: ssz_vector(value.logs_bloom.clone())?,
+            prev_randao: value.prev_randao.0,
+            block_number: value.block_number,
+            gas_limit: value.gas_limit,
+            gas_used: value.gas_used,
+            timestamp: value.timestamp,
+            extra_data: ssz_list(value.extra_data.clone())?,
+            base_fee_per_gas: value.base_fee_per_gas,
+            block_hash: value.block_hash.0,
+            transactions_root: value.transactions_root.0,
+            withdrawals_root: value.withdrawals_root.0,
+            blob_gas_used: value.blob_gas_used,
+            excess_blob_gas: value.excess_blob_gas,
+        })
+    }
+}
+
+impl TryFrom<&SyncCommittee> for SSZSyncCommittee {
+    type Error = LightClientError;
+    fn try_from(value: &SyncCommittee) -> Result<Self, LightClientError> {
+        let pubkeys = value
+            .pubkeys
+            .iter()
+            .map(|pubkey| ssz_vector(pubkey.0.to_vec()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(SSZSyncCommittee {
+            pubkeys: ssz_vector(pubkeys)?,
+            aggregate_pubkey: ssz_vector(value.aggregate_pubkey.0.to_vec())?,
+        })
+    }
+}
+
+impl TryFrom<&SyncAggregate> for SSZSyncAggregate {
+    type Error = LightClientError;
+    fn try_from(value: &SyncAggregate) -> Result<Self, LightClientError> {
+        Ok(SSZSyncAggregate {
+            sync_committee_bits: value.sync_committee_bits.clone(),
+            sync_committee_signature: ssz_vector(value.sync_committee_signature.clone())?,
+        })
+    }
+}
+
+impl TryFrom<&SyncCommitteePeriodUpdate> for SSZSyncCommitteePeriodUpdate {
+    type Error = LightClientError;
+    fn try_from(value: &SyncCommitteePeriodUpdate) -> Result<Self, LightClientError> {
+        Ok(SSZSyncCommitteePeriodUpdate {
+            attested_header: (&value.attested_header).try_into()?,
+            next_sync_committee: (&value.next_sync_committee).try_into()?,
+            next_sync_committee_branch: ssz_branch(&value.next_sync_committee_branch)?,
+            finalized_header: (&value.finalized_header).try_into()?,
+            finality_branch: ssz_branch(&value.finality_branch)?,
+            sync_aggregate: (&value.sync_aggregate).try_into()?,
+            signature_slot: value.signature_slot,
+        })
+    }
+}
+
+impl TryFrom<&SyncCommitteePeriodUpdate> for SSZSyncCommitteePeriodUpdateCapella {
+    type Error = LightClientError;
+    fn try_from(value: &SyncCommitteePeriodUpdate) -> Result<Self, LightClientError> {
+        Ok(SSZSyncCommitteePeriodUpdateCapella {
+            attested_header: (&value.attested_header).try_into()?,
+            next_sync_committee: (&value.next_sync_committee).try_into()?,
+            next_sync_committee_branch: ssz_branch(&value.next_sync_committee_branch)?,
+            finalized_header: (&value.finalized_header).try_into()?,
+            finality_branch: ssz_branch(&value.finality_branch)?,
+            sync_aggregate: (&value.sync_aggregate).try_into()?,
+            signature_slot: value.signature_slot,
+        })
+    }
+}
+
+impl TryFrom<&SyncCommitteePeriodUpdate> for SSZSyncCommitteePeriodUpdateDeneb {
+    type Error = LightClientError;
+    fn try_from(value: &SyncCommitteePeriodUpdate) -> Result<Self, LightClientError> {
+        Ok(SSZSyncCommitteePeriodUpdateDeneb {
+            attested_header: (&value.attested_header).try_into()?,
+            next_sync_committee: (&value.next_sync_committee).try_into()?,
+            next_sync_committee_branch: ssz_branch(&value.next_sync_committee_branch)?,
+            finalized_header: (&value.finalized_header).try_into()?,
+            finality_branch: ssz_branch(&value.finality_branch)?,
+            sync_aggregate: (&value.sync_aggregate).try_into()?,
+            signature_slot: value.signature_slot,
+        })
+    }
+}
+
+impl TryFrom<&SyncCommitteePeriodUpdate> for SSZSyncCommitteePeriodUpdateElectra {
+    type Error = LightClientError;
+    fn try_from(value: &SyncCommitteePeriodUpdate) -> Result<Self, LightClientError> {
+        Ok(SSZSyncCommitteePeriodUpdateElectra {
+            attested_header: (&value.attested_header).try_into()?,
+            next_sync_committee: (&value.next_sync_committee).try_into()?,
+            next_sync_committee_branch: ssz_branch(&value.next_sync_committee_branch)?,
+            finalized_header: (&value.finalized_header).try_into()?,
+            finality_branch: ssz_branch(&value.finality_branch)?,
+            sync_aggregate: (&value.sync_aggregate).try_into()?,
+            signature_slot: value.signature_slot,
+        })
+    }
+}
+
+impl TryFrom<&FinalizedHeaderUpdate> for SSZFinalizedHeaderUpdate {
+    type Error = LightClientError;
+    fn try_from(value: &FinalizedHeaderUpdate) -> Result<Self, LightClientError> {
+        Ok(SSZFinalizedHeaderUpdate {
+            attested_header: (&value.attested_header).try_into()?,
+            finalized_header: (&value.finalized_header).try_into()?,
+            finality_branch: ssz_branch(&value.finality_branch)?,
+            sync_aggregate: (&value.sync_aggregate).try_into()?,
+            signature_slot: value.signature_slot,
+        })
+    }
+}
+
+impl TryFrom<&FinalizedHeaderUpdate> for SSZFinalizedHeaderUpdateCapella {
+    type Error = LightClientError;
+    fn try_from(value: &FinalizedHeaderUpdate) -> Result<Self, LightClientError> {
+        Ok(SSZFinalizedHeaderUpdateCapella {
+            attested_header: (&value.attested_header).try_into()?,
+            finalized_header: (&value.finalized_header).try_into()?,
+            finality_branch: ssz_branch(&value.finality_branch)?,
+            sync_aggregate: (&value.sync_aggregate).try_into()?,
+            signature_slot: value.signature_slot,
+        })
+    }
+}
+
+impl TryFrom<&FinalizedHeaderUpdate> for SSZFinalizedHeaderUpdateDeneb {
+    type Error = LightClientError;
+    fn try_from(value: &FinalizedHeaderUpdate) -> Result<Self, LightClientError> {
+        Ok(SSZFinalizedHeaderUpdateDeneb {
+            attested_header: (&value.attested_header).try_into()?,
+            finalized_header: (&value.finalized_header).try_into()?,
+            finality_branch: ssz_branch(&value.finality_branch)?,
+            sync_aggregate: (&value.sync_aggregate).try_into()?,
+            signature_slot: value.signature_slot,
+        })
+    }
+}
+
+impl TryFrom<&FinalizedHeaderUpdate> for SSZFinalizedHeaderUpdateElectra {
+    type Error = LightClientError;
+    fn try_from(value: &FinalizedHeaderUpdate) -> Result<Self, LightClientError> {
+        Ok(SSZFinalizedHeaderUpdateElectra {
+            attested_header: (&value.attested_header).try_into()?,
+            finalized_header: (&value.finalized_header).try_into()?,
+            finality_branch: ssz_branch(&value.finality_branch)?,
+            sync_aggregate: (&value.sync_aggregate).try_into()?,
+            signature_slot: value.signature_slot,
+        })
+    }
+}
+
+impl TryFrom<&LightClientOptimisticUpdate> for SSZLightClientOptimisticUpdate {
+    type Error = LightClientError;
+    fn try_from(value: &LightClientOptimisticUpdate) -> Result<Self, LightClientError> {
+        Ok(SSZLightClientOptimisticUpdate {
+            attested_header: (&value.attested_header).try_into()?,
+            sync_aggregate: (&value.sync_aggregate).try_into()?,
+            signature_slot: value.signature_slot,
+        })
+    }
+}
+
+impl TryFrom<&LightClientOptimisticUpdate> for SSZLightClientOptimisticUpdateCapella {
+    type Error = LightClientError;
+    fn try_from(value: &LightClientOptimisticUpdate) -> Result<Self, LightClientError> {
+        Ok(SSZLightClientOptimisticUpdateCapella {
+            attested_header: (&value.attested_header).try_into()?,
+            sync_aggregate: (&value.sync_aggregate).try_into()?,
+            signature_slot: value.signature_slot,
+        })
+    }
+}
+
+impl TryFrom<&LightClientOptimisticUpdate> for SSZLightClientOptimisticUpdateDeneb {
+    type Error = LightClientError;
+    fn try_from(value: &LightClientOptimisticUpdate) -> Result<Self, LightClientError> {
+        Ok(SSZLightClientOptimisticUpdateDeneb {
+            attested_header: (&value.attested_header).try_into()?,
+            sync_aggregate: (&value.sync_aggregate).try_into()?,
+            signature_slot: value.signature_slot,
+        })
+    }
+}
+
+impl TryFrom<&LightClientBootstrap> for SSZLightClientBootstrap {
+    type Error = LightClientError;
+    fn try_from(value: &LightClientBootstrap) -> Result<Self, LightClientError> {
+        Ok(SSZLightClientBootstrap {
+            header: (&value.header).try_into()?,
+            current_sync_committee: (&value.current_sync_committee).try_into()?,
+            current_sync_committee_branch: ssz_branch(&value.current_sync_committee_branch)?,
+        })
+    }
+}
+
+impl TryFrom<&LightClientBootstrap> for SSZLightClientBootstrapCapella {
+    type Error = LightClientError;
+    fn try_from(value: &LightClientBootstrap) -> Result<Self, LightClientError> {
+        Ok(SSZLightClientBootstrapCapella {
+            header: (&value.header).try_into()?,
+            current_sync_committee: (&value.current_sync_committee).try_into()?,
+            current_sync_committee_branch: ssz_branch(&value.current_sync_committee_branch)?,
+        })
+    }
+}
+
+impl TryFrom<&LightClientBootstrap> for SSZLightClientBootstrapDeneb {
+    type Error = LightClientError;
+    fn try_from(value: &LightClientBootstrap) -> Result<Self, LightClientError> {
+        Ok(SSZLightClientBootstrapDeneb {
+            header: (&value.header).try_into()?,
+            current_sync_committee: (&value.current_sync_committee).try_into()?,
+            current_sync_committee_branch: ssz_branch(&value.current_sync_committee_branch)?,
+        })
+    }
+}
+
+impl TryFrom<&LightClientBootstrap> for SSZLightClientBootstrapElectra {
+    type Error = LightClientError;
+    fn try_from(value: &LightClientBootstrap) -> Result<Self, LightClientError> {
+        Ok(SSZLightClientBootstrapElectra {
+            header: (&value.header).try_into()?,
+            current_sync_committee: (&value.current_sync_committee).try_into()?,
+            current_sync_committee_branch: ssz_branch(&value.current_sync_committee_branch)?,
+        })
+    }
+}
+
+pub(crate) fn encode_ssz<T: SimpleSerializeTrait>(
+    value: &T,
+    field: &'static str,
+) -> Result<Vec<u8>, LightClientError> {
+    ssz_rs::serialize(value).map_err(|_e| LightClientError::Encode { field })
+}
+
+fn ssz_vector<T: SimpleSerializeTrait + Clone, const N: usize>(
+    items: Vec<T>,
+) -> Result<Vector<T, N>, LightClientError> {
+    let actual = items.len();
+    if actual != N {
+        return Err(LightClientError::InvalidLength { expected: N, actual });
+    }
+    Vector::try_from(items).map_err(|_e| LightClientError::InvalidLength { expected: N, actual })
+}
+
+fn ssz_list<T: SimpleSerializeTrait + Clone, const N: usize>(
+    items: Vec<T>,
+) -> Result<List<T, N>, LightClientError> {
+    let actual = items.len();
+    List::try_from(items).map_err(|_e| LightClientError::InvalidLength { expected: N, actual })
+}
+
+fn ssz_branch<const N: usize>(branch: &[H256]) -> Result<Vector<[u8; 32], N>, LightClientError> {
+    ssz_vector(branch.iter().map(|node| node.0).collect())
+}
+
+// Headers from before Capella have no execution branch, newer containers carry it zeroed.
+fn ssz_execution_branch(
+    branch: &[H256],
+) -> Result<Vector<[u8; 32], { EXECUTION_PAYLOAD_DEPTH as usize }>, LightClientError> {
+    if branch.is_empty() {
+        return Ok(Vector::default());
+    }
+    ssz_branch(branch)
+}
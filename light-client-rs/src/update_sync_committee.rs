@@ -1,7 +1,12 @@
 use super::types::*;
 use super::utils::*;
+use super::view::{SyncCommitteePeriodUpdateView, SyncCommitteeView};
+use crate::bls::BlsVerifier;
+use crate::chain_spec::ChainSpec;
+use crate::error::{LightClientError, MerkleBranch, UpdateChainError};
+use crate::policy::VerificationPolicy;
 use alloc::vec::Vec;
-use alloc::string::String;
+use crate::hasher::Hasher;
 
 
 macro_rules! tryprintln {
@@ -12,105 +17,230 @@ macro_rules! tryprintln {
 
 
 
-pub fn process_sync_committee_period_update(
+pub fn process_sync_committee_period_update<V: BlsVerifier, H: Hasher>(
     prev_update: SyncCommitteePeriodUpdate,
     update: SyncCommitteePeriodUpdate,
-    validators_root: H256,
-) -> Result<(SyncCommittee, BeaconHeader), String> {
+    policy: &VerificationPolicy,
+    spec: &ChainSpec<H>,
+) -> Result<(SyncCommittee, LightClientHeader), LightClientError> {
+    verify_sync_committee_period_update::<V, H>(&prev_update, &update, policy, spec)?;
+
+    // Self::store_sync_committee(current_period + 1, update.next_sync_committee);
+    // Self::store_finalized_header(block_root, update.finalized_header);
+    Ok((update.next_sync_committee, update.finalized_header))
+}
+
+// Borrows both updates, so that a chain can keep every verified update as the next one's
+// previous update.
+fn verify_sync_committee_period_update<V: BlsVerifier, H: Hasher>(
+    prev_update: &SyncCommitteePeriodUpdate,
+    update: &SyncCommitteePeriodUpdate,
+    policy: &VerificationPolicy,
+    spec: &ChainSpec<H>,
+) -> Result<(), LightClientError> {
+    verify_update_continuity(
+        prev_update.attested_header.beacon.slot,
+        prev_update.finalized_header.beacon.slot,
+        update.signature_slot,
+        update.attested_header.beacon.slot,
+        update.finalized_header.beacon.slot,
+    )?;
+    tryprintln!("update follows prev_update");
     let sync_committee_bits =
-        get_sync_committee_bits(update.sync_aggregate.sync_committee_bits.clone())?;
+        get_sync_committee_bits(&update.sync_aggregate.sync_committee_bits)?;
     //     .map_err(|_| DispatchError::Other("Couldn't process sync committee bits"))?;
     tryprintln!("got sync committee bits");
-    sync_committee_participation_is_supermajority(sync_committee_bits.clone())?;
-    tryprintln!("sync committee participation is supermajority");
-    verify_sync_committee(
-        update.next_sync_committee.clone(),
-        update.next_sync_committee_branch,
-        update.finalized_header.state_root,
-        NEXT_SYNC_COMMITTEE_DEPTH,
-        NEXT_SYNC_COMMITTEE_INDEX,
+    policy.verify_finality_participation(get_sync_committee_sum(&sync_committee_bits))?;
+    tryprintln!("sync committee participation is sufficient");
+    verify_light_client_header(&update.attested_header, spec)?;
+    verify_light_client_header(&update.finalized_header, spec)?;
+    let gindex = next_sync_committee_gindex(spec, update.attested_header.beacon.slot);
+    verify_sync_committee::<H>(
+        &update.next_sync_committee,
+        update.next_sync_committee_branch.iter().copied(),
+        update.attested_header.beacon.state_root,
+        gindex,
+        MerkleBranch::NextSyncCommittee,
     )?;
     tryprintln!("verified sync committee");
-    let block_root: H256 = hash_tree_root_beacon_header(update.finalized_header.clone())?.into();
-    verify_header(
+    let block_root = update.finalized_header.beacon.hash_tree_root_with::<H>();
+    let gindex = finalized_root_gindex(spec, update.attested_header.beacon.slot);
+    verify_header::<H>(
         block_root,
-        update.finality_branch,
-        update.attested_header.state_root,
-        FINALIZED_ROOT_DEPTH,
-        FINALIZED_ROOT_INDEX,
+        update.finality_branch.iter().copied(),
+        update.attested_header.beacon.state_root,
+        gindex,
+        MerkleBranch::Finality,
     )?;
 
     // let current_period = compute_current_sync_period(update.attested_header.slot);
     // let current_sync_committee = Self::get_sync_committee_for_period(current_period)?;
-    let current_sync_committee = prev_update.next_sync_committee;
-    // let validators_root = <ValidatorsRoot<T>>::get();
-
-    verify_signed_header(
-        sync_committee_bits,
-        update.sync_aggregate.sync_committee_signature,
-        current_sync_committee.pubkeys,
-        update.fork_version,
-        update.attested_header,
-        validators_root,
+    verify_signature_period(prev_update.attested_header.beacon.slot, update.signature_slot)?;
+    let participant_pubkey = prev_update
+        .next_sync_committee
+        .aggregate_participants::<V>(&sync_committee_bits)?;
+    verify_sync_aggregate_signature::<V, H>(
+        &participant_pubkey,
+        &update.sync_aggregate.sync_committee_signature,
+        update.signature_slot,
+        update.attested_header.beacon.clone(),
+        spec,
     )?;
-
-    // Self::store_sync_committee(current_period + 1, update.next_sync_committee);
-    // Self::store_finalized_header(block_root, update.finalized_header);
     tryprintln!("verified signed header");
-    Ok((update.next_sync_committee, update.finalized_header))
+    Ok(())
 }
 
+/// Same as [`process_sync_committee_period_update`] over the encoded updates, the committees,
+/// branches and sync aggregate are read in place instead of being decoded.
+pub fn process_sync_committee_period_update_view<'a, V: BlsVerifier, H: Hasher>(
+    prev_update: &SyncCommitteePeriodUpdateView<'_>,
+    update: &SyncCommitteePeriodUpdateView<'a>,
+    policy: &VerificationPolicy,
+    spec: &ChainSpec<H>,
+) -> Result<(SyncCommitteeView<'a>, LightClientHeader), LightClientError> {
+    let prev_attested_slot = prev_update.attested_header()?.beacon.slot;
+    let attested_header = update.attested_header()?;
+    let finalized_header = update.finalized_header()?;
+    verify_update_continuity(
+        prev_attested_slot,
+        prev_update.finalized_header()?.beacon.slot,
+        update.signature_slot(),
+        attested_header.beacon.slot,
+        finalized_header.beacon.slot,
+    )?;
+    let participants = update
+        .sync_committee_bits()
+        .iter()
+        .map(|byte| byte.count_ones() as u64)
+        .sum();
+    policy.verify_finality_participation(participants)?;
+    verify_light_client_header(&attested_header, spec)?;
+    verify_light_client_header(&finalized_header, spec)?;
+    let gindex = next_sync_committee_gindex(spec, attested_header.beacon.slot);
+    verify_header::<H>(
+        update.next_sync_committee().hash_tree_root_with::<H>()?.into(),
+        update.next_sync_committee_branch(),
+        attested_header.beacon.state_root,
+        gindex,
+        MerkleBranch::NextSyncCommittee,
+    )?;
+    let block_root = finalized_header.beacon.hash_tree_root_with::<H>();
+    let gindex = finalized_root_gindex(spec, attested_header.beacon.slot);
+    verify_header::<H>(
+        block_root,
+        update.finality_branch(),
+        attested_header.beacon.state_root,
+        gindex,
+        MerkleBranch::Finality,
+    )?;
 
-fn sync_committee_participation_is_supermajority(
-    sync_committee_bits: Vec<u8>,
-) -> Result<(), String> {
-    let sync_committee_sum = get_sync_committee_sum(sync_committee_bits.clone());
-    if sync_committee_sum * 3 >= sync_committee_bits.clone().len() as u64 * 2 {
-        return Ok(());
-    } else {
-        return Err("Sync committee participation is not supermajority".into());
-    }
-}
+    verify_signature_period(prev_attested_slot, update.signature_slot())?;
+    let participant_pubkey = prev_update
+        .next_sync_committee()
+        .aggregate_participants::<V>(update.sync_committee_bits())?;
+    verify_sync_aggregate_signature::<V, H>(
+        &participant_pubkey,
+        update.sync_committee_signature(),
+        update.signature_slot(),
+        attested_header.beacon,
+        spec,
+    )?;
 
+    Ok((update.next_sync_committee(), finalized_header))
+}
 
 
-fn verify_sync_committee(
-    sync_committee: SyncCommittee,
-    sync_committee_branch: Vec<H256>,
-    header_state_root: H256,
-    depth: u64,
-    index: u64,
-) -> Result<(), String> {
-    let sync_committee_root = hash_tree_root_sync_committee(sync_committee)?;
-
-    if is_valid_merkle_branch(
-        sync_committee_root.into(),
-        sync_committee_branch,
-        depth,
-        index,
-        header_state_root,
-    ) {
-        return Ok(());
-    } else {
-        return Err("Sync committee merkle branch is invalid".into());
+/// Walks the sync committees forward from a trusted `checkpoint` update, verifying every update
+/// against the one before it. Returns the committee and finalized header of the last update, or
+/// the checkpoint's own when `updates` is empty.
+pub fn process_sync_committee_update_chain<V: BlsVerifier, H: Hasher>(
+    checkpoint: SyncCommitteePeriodUpdate,
+    updates: Vec<SyncCommitteePeriodUpdate>,
+    policy: &VerificationPolicy,
+    spec: &ChainSpec<H>,
+) -> Result<(SyncCommittee, LightClientHeader), UpdateChainError> {
+    let mut prev_update = checkpoint;
+    for (index, update) in updates.into_iter().enumerate() {
+        verify_sync_committee_period_update::<V, H>(&prev_update, &update, policy, spec)
+            .map_err(|error| UpdateChainError { index: Some(index), error })?;
+        prev_update = update;
     }
+    Ok((prev_update.next_sync_committee, prev_update.finalized_header))
 }
 
-fn hash_tree_root_sync_committee(sync_committee: SyncCommittee) -> Result<[u8; 32], String> {
-    let mut pubkeys_vec = Vec::new();
-
-    for pubkey in sync_committee.pubkeys.iter() {
-        let conv_pubkey = Vector::<u8, 48>::from_iter(pubkey.0);
-
-        pubkeys_vec.push(conv_pubkey);
+/// Same as [`process_sync_committee_update_chain`] over the encoded updates.
+pub fn process_sync_committee_update_chain_view<'a, V: BlsVerifier, H: Hasher>(
+    checkpoint: SyncCommitteePeriodUpdateView<'a>,
+    updates: impl IntoIterator<Item = SyncCommitteePeriodUpdateView<'a>>,
+    policy: &VerificationPolicy,
+    spec: &ChainSpec<H>,
+) -> Result<(SyncCommitteeView<'a>, LightClientHeader), UpdateChainError> {
+    let mut prev_update = checkpoint;
+    let mut last = None;
+    for (index, update) in updates.into_iter().enumerate() {
+        last = Some(
+            process_sync_committee_period_update_view::<V, H>(&prev_update, &update, policy, spec)
+                .map_err(|error| UpdateChainError { index: Some(index), error })?,
+        );
+        prev_update = update;
+    }
+    match last {
+        Some(last) => Ok(last),
+        None => {
+            let finalized_header = checkpoint
+                .finalized_header()
+                .map_err(|error| UpdateChainError { index: None, error })?;
+            Ok((checkpoint.next_sync_committee(), finalized_header))
+        }
     }
+}
 
-    let pubkeys = Vector::<Vector<u8, 48>, { SYNC_COMMITTEE_SIZE }>::from_iter(pubkeys_vec.clone());
+/// Checks that an update is ordered internally and picks up exactly one sync committee period
+/// after the previous one, so that the previous update's next sync committee is the committee that
+/// signed it.
+fn verify_update_continuity(
+    prev_attested_slot: u64,
+    prev_finalized_slot: u64,
+    signature_slot: u64,
+    attested_slot: u64,
+    finalized_slot: u64,
+) -> Result<(), LightClientError> {
+    if !(signature_slot > attested_slot && attested_slot >= finalized_slot) {
+        return Err(LightClientError::InvalidSlotOrder);
+    }
+    if finalized_slot <= prev_finalized_slot {
+        return Err(LightClientError::InvalidSlotOrder);
+    }
 
-    let agg = Vector::<u8, 48>::from_iter(sync_committee.aggregate_pubkey.0);
+    // The next sync committee of an update is proven against its attested state, keep the chain
+    // at one period per step so that every committee is used for the period it was elected for.
+    let store_period = compute_sync_committee_period_at_slot(prev_attested_slot);
+    let update_period = compute_sync_committee_period_at_slot(attested_slot);
+    if update_period != store_period + 1 {
+        return Err(LightClientError::InvalidSyncCommitteePeriod {
+            store_period,
+            update_period,
+        });
+    }
+    Ok(())
+}
 
-    hash_tree_root(SSZSyncCommittee {
-        pubkeys: pubkeys,
-        aggregate_pubkey: agg,
-    })
+/// Checks that the next sync committee of the previous update, attested at `prev_attested_slot`,
+/// is the one that signed at `signature_slot`, following the spec's choice between the current and
+/// the next sync committee of the store. Only the next committee of the previous update is known
+/// here, the current one of its period would have come from the update before it.
+fn verify_signature_period(
+    prev_attested_slot: u64,
+    signature_slot: u64,
+) -> Result<(), LightClientError> {
+    let store_period = compute_sync_committee_period_at_slot(prev_attested_slot);
+    let signature_period = compute_sync_committee_period_at_slot(signature_slot);
+    if signature_period == store_period + 1 {
+        Ok(())
+    } else {
+        Err(LightClientError::InvalidSyncCommitteePeriod {
+            store_period,
+            update_period: signature_period,
+        })
+    }
 }
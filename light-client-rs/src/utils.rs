@@ -1,171 +1,376 @@
 use super::types::*;
+use crate::bls::{BlsVerifier, PreparedSyncCommittee};
+use crate::chain_spec::{ChainSpec, ForkName};
+use crate::error::{LightClientError, MerkleBranch};
+use crate::hasher::Hasher;
+use crate::merkle::{is_valid_merkle_branch, GeneralizedIndex};
 
+use alloc::vec;
 use alloc::vec::Vec;
-use alloc::string::String;
-
-pub(super) fn get_sync_committee_bits(bitv: Bitvector::<{ SYNC_COMMITTEE_SIZE }>) -> Result<Vec<u8>, String> {
-    // tryprintln!("About to deserialize");
-    // let bitv = Bitvector::<{ SYNC_COMMITTEE_SIZE }>::deserialize(&bits_hex).unwrap();
-        // .map_err(|_e| "DeserializeError".to_string())?;
-    // tryprintln!("did deserialize");
 
+pub(super) fn get_sync_committee_bits(
+    bitv: &Bitvector<{ SYNC_COMMITTEE_SIZE }>,
+) -> Result<Vec<u8>, LightClientError> {
     let result = bitv
         .iter()
-        .map(|bit| if bit == true { 1 } else { 0 })
+        .map(|bit| if bit { 1 } else { 0 })
         .collect::<Vec<_>>();
 
     Ok(result)
 }
 
-pub(super)fn get_sync_committee_sum(sync_committee_bits: Vec<u8>) -> u64 {
+pub(super) fn get_sync_committee_sum(sync_committee_bits: &[u8]) -> u64 {
     sync_committee_bits
         .iter()
         .fold(0, |acc: u64, x| acc + *x as u64)
 }
 
-pub(super) fn hash_tree_root_beacon_header(beacon_header: BeaconHeader) -> Result<[u8; 32], String> {
-    hash_tree_root(get_ssz_beacon_header(beacon_header)?)
+pub fn compute_epoch_at_slot(slot: u64) -> u64 {
+    slot / SLOTS_PER_EPOCH
+}
+
+pub fn compute_sync_committee_period(epoch: u64) -> u64 {
+    epoch / EPOCHS_PER_SYNC_COMMITTEE_PERIOD
+}
+
+pub fn compute_sync_committee_period_at_slot(slot: u64) -> u64 {
+    compute_sync_committee_period(compute_epoch_at_slot(slot))
+}
+
+/// Generalized index of the finalized checkpoint root in the state at `slot`.
+pub fn finalized_root_gindex<H: Hasher>(spec: &ChainSpec<H>, slot: u64) -> GeneralizedIndex {
+    if spec.fork_at_slot(slot) >= ForkName::Electra {
+        FINALIZED_ROOT_GINDEX_ELECTRA
+    } else {
+        FINALIZED_ROOT_GINDEX
+    }
+}
+
+/// Generalized index of the current sync committee in the state at `slot`.
+pub fn current_sync_committee_gindex<H: Hasher>(
+    spec: &ChainSpec<H>,
+    slot: u64,
+) -> GeneralizedIndex {
+    if spec.fork_at_slot(slot) >= ForkName::Electra {
+        CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA
+    } else {
+        CURRENT_SYNC_COMMITTEE_GINDEX
+    }
+}
+
+/// Generalized index of the next sync committee in the state at `slot`.
+pub fn next_sync_committee_gindex<H: Hasher>(spec: &ChainSpec<H>, slot: u64) -> GeneralizedIndex {
+    if spec.fork_at_slot(slot) >= ForkName::Electra {
+        NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA
+    } else {
+        NEXT_SYNC_COMMITTEE_GINDEX
+    }
 }
 
+/// Generalized index of the `block_roots` vector in the state at `slot`.
+pub fn block_roots_gindex<H: Hasher>(spec: &ChainSpec<H>, slot: u64) -> GeneralizedIndex {
+    if spec.fork_at_slot(slot) >= ForkName::Electra {
+        BLOCK_ROOTS_GINDEX_ELECTRA
+    } else {
+        BLOCK_ROOTS_GINDEX
+    }
+}
 
-pub(super) fn hash_tree_root<T: SimpleSerializeTrait>(mut object: T) -> Result<[u8; 32], String> {
-    match object.hash_tree_root() {
-        Ok(node) => node
-            .as_bytes()
-            .try_into()
-            .map_err(|_| "Invalid hash tree root".into()),
-        Err(_e) => Err("MerkleizationError::HashTreeRootError".into()),
+/// Generalized index of the `historical_summaries` list in the state at `slot`.
+pub fn historical_summaries_gindex<H: Hasher>(spec: &ChainSpec<H>, slot: u64) -> GeneralizedIndex {
+    if spec.fork_at_slot(slot) >= ForkName::Electra {
+        HISTORICAL_SUMMARIES_GINDEX_ELECTRA
+    } else {
+        HISTORICAL_SUMMARIES_GINDEX
     }
 }
 
-pub(super) fn get_ssz_beacon_header(beacon_header: BeaconHeader) -> Result<SSZBeaconBlockHeader, String> {
-    Ok(SSZBeaconBlockHeader {
-        slot: beacon_header.slot,
-        proposer_index: beacon_header.proposer_index,
-        parent_root: beacon_header
-            .parent_root
-            .as_bytes()
-            .try_into()
-            .map_err(|_| "MerkleizationError::InvalidLength")?,
-        state_root: beacon_header
-            .state_root
-            .as_bytes()
-            .try_into()
-            .map_err(|_| "MerkleizationError::InvalidLength")?,
-        body_root: beacon_header
-            .body_root
-            .as_bytes()
-            .try_into()
-            .map_err(|_| "MerkleizationError::InvalidLength")?,
-    })
+pub(super) fn hash_tree_root_beacon_header<H: Hasher>(beacon_header: &BeaconHeader) -> [u8; 32] {
+    merkleize::<H>(vec![
+        u64_chunk(beacon_header.slot),
+        u64_chunk(beacon_header.proposer_index),
+        beacon_header.parent_root.0,
+        beacon_header.state_root.0,
+        beacon_header.body_root.0,
+    ])
 }
 
-pub(super) fn verify_header(
+pub(super) fn verify_header<H: Hasher>(
     block_root: H256,
-    proof_branch: Vec<H256>,
+    proof_branch: impl IntoIterator<Item = H256>,
     attested_header_state_root: H256,
-    depth: u64,
-    index: u64,
-) -> Result<(), String> {
-    if is_valid_merkle_branch(
-        block_root,
-        proof_branch,
-        depth,
-        index,
-        attested_header_state_root,
-    ) {
-        return Ok(());
+    gindex: GeneralizedIndex,
+    branch: MerkleBranch,
+) -> Result<(), LightClientError> {
+    if is_valid_merkle_branch::<H>(block_root, proof_branch, gindex, attested_header_state_root) {
+        Ok(())
     } else {
-        return Err("Header merkle branch is invalid".into());
+        Err(LightClientError::InvalidMerkleBranch(branch))
     }
 }
 
-pub(super)fn verify_signed_header(
-    sync_committee_bits: Vec<u8>,
-    sync_committee_signature: Vec<u8>,
-    sync_committee_pubkeys: Vec<PublicKey>,
-    fork_version: ForkVersion,
-    header: BeaconHeader,
-    validators_root: H256,
-) -> Result<(), String> {
-    let mut participant_pubkeys: Vec<PublicKey> = Vec::new();
-    // Gathers all the pubkeys of the sync committee members that participated in siging the header.
-    for (bit, pubkey) in sync_committee_bits
-        .iter()
-        .zip(sync_committee_pubkeys.iter())
-    {
-        if *bit == 1 as u8 {
-            let pubk = pubkey.clone();
-            participant_pubkeys.push(pubk);
+/// Checks that the execution payload header of `header` is the one committed to in its beacon
+/// block body, or that there is none if the header predates Capella.
+pub fn is_valid_light_client_header<H: Hasher>(
+    header: &LightClientHeader,
+    spec: &ChainSpec<H>,
+) -> bool {
+    verify_light_client_header(header, spec).is_ok()
+}
+
+pub(super) fn verify_light_client_header<H: Hasher>(
+    header: &LightClientHeader,
+    spec: &ChainSpec<H>,
+) -> Result<(), LightClientError> {
+    let fork = spec.fork_at_slot(header.beacon.slot);
+
+    let execution = match &header.execution {
+        Some(execution) if fork >= ForkName::Capella => execution,
+        // Newer containers carry an empty payload for headers from before Capella.
+        Some(execution) if !execution.is_empty() => {
+            return Err(LightClientError::InvalidLightClientHeader)
+        }
+        None if fork >= ForkName::Capella => {
+            return Err(LightClientError::InvalidLightClientHeader)
+        }
+        _ => {
+            if header
+                .execution_branch
+                .iter()
+                .any(|node| *node != H256::default())
+            {
+                return Err(LightClientError::InvalidLightClientHeader);
+            }
+            return Ok(());
         }
+    };
+
+    if fork < ForkName::Deneb && (execution.blob_gas_used != 0 || execution.excess_blob_gas != 0) {
+        return Err(LightClientError::InvalidLightClientHeader);
     }
 
-    let domain_type = DOMAIN_SYNC_COMMITTEE.to_vec();
-    // Domains are used for for seeds, for signatures, and for selecting aggregators.
-    let domain = compute_domain(domain_type, Some(fork_version), validators_root)?;
-    // Hash tree root of SigningData - object root + domain
-    let signing_root = compute_signing_root(header, domain)?;
+    let execution_root = hash_tree_root_execution_payload_header::<H>(execution, fork)?;
+    if is_valid_merkle_branch::<H>(
+        execution_root.into(),
+        header.execution_branch.iter().copied(),
+        EXECUTION_PAYLOAD_GINDEX,
+        header.beacon.body_root,
+    ) {
+        Ok(())
+    } else {
+        Err(LightClientError::InvalidMerkleBranch(
+            MerkleBranch::Execution,
+        ))
+    }
+}
 
-    // Verify sync committee aggregate signature.
-    bls_fast_aggregate_verify(participant_pubkeys, signing_root, sync_committee_signature)?;
+// Root of the Capella `ExecutionPayloadHeader` container, or of the Deneb one with the blob gas
+// fields from Deneb on.
+pub(super) fn hash_tree_root_execution_payload_header<H: Hasher>(
+    header: &ExecutionPayloadHeader,
+    fork: ForkName,
+) -> Result<[u8; 32], LightClientError> {
+    if header.logs_bloom.len() != BYTES_PER_LOGS_BLOOM {
+        return Err(LightClientError::InvalidLength {
+            expected: BYTES_PER_LOGS_BLOOM,
+            actual: header.logs_bloom.len(),
+        });
+    }
+    if header.extra_data.len() > MAX_EXTRA_DATA_BYTES {
+        return Err(LightClientError::InvalidLength {
+            expected: MAX_EXTRA_DATA_BYTES,
+            actual: header.extra_data.len(),
+        });
+    }
 
-    Ok(())
+    let mut fee_recipient = [0u8; 32];
+    fee_recipient[..20].copy_from_slice(&header.fee_recipient);
+    let logs_bloom = merkleize::<H>(
+        header
+            .logs_bloom
+            .chunks_exact(32)
+            .map(|chunk| chunk.try_into().unwrap()),
+    );
+    // The extra data list fits a single chunk, its root mixes in the length.
+    let mut extra_data = [0u8; 32];
+    extra_data[..header.extra_data.len()].copy_from_slice(&header.extra_data);
+    let extra_data = H::hash_pair(&extra_data, &u64_chunk(header.extra_data.len() as u64));
+
+    let mut fields = vec![
+        header.parent_hash.0,
+        fee_recipient,
+        header.state_root.0,
+        header.receipts_root.0,
+        logs_bloom,
+        header.prev_randao.0,
+        u64_chunk(header.block_number),
+        u64_chunk(header.gas_limit),
+        u64_chunk(header.gas_used),
+        u64_chunk(header.timestamp),
+        extra_data,
+        header.base_fee_per_gas,
+        header.block_hash.0,
+        header.transactions_root.0,
+        header.withdrawals_root.0,
+    ];
+    if fork >= ForkName::Deneb {
+        fields.push(u64_chunk(header.blob_gas_used));
+        fields.push(u64_chunk(header.excess_blob_gas));
+    }
+    Ok(merkleize::<H>(fields))
 }
 
-pub(super)fn is_valid_merkle_branch(
-    leaf: H256,
-    branch: Vec<H256>,
-    depth: u64,
-    index: u64,
-    root: Root,
-) -> bool {
-    if branch.len() != depth as usize {
-        // log::error!(target: "ethereum-beacon-client", "Merkle proof branch length doesn't match depth.");
-
-        return false;
+pub(super) fn verify_sync_committee<H: Hasher>(
+    sync_committee: &SyncCommittee,
+    sync_committee_branch: impl IntoIterator<Item = H256>,
+    header_state_root: H256,
+    gindex: GeneralizedIndex,
+    branch: MerkleBranch,
+) -> Result<(), LightClientError> {
+    let sync_committee_root = hash_tree_root_sync_committee::<H>(sync_committee)?;
+
+    if is_valid_merkle_branch::<H>(
+        sync_committee_root.into(),
+        sync_committee_branch,
+        gindex,
+        header_state_root,
+    ) {
+        Ok(())
+    } else {
+        Err(LightClientError::InvalidMerkleBranch(branch))
     }
-    let mut value = leaf;
-    if leaf.as_bytes().len() < 32 as usize {
-        // log::error!(target: "ethereum-beacon-client", "Merkle proof leaf not 32 bytes.");
+}
+
+pub(super) fn hash_tree_root_sync_committee<H: Hasher>(
+    sync_committee: &SyncCommittee,
+) -> Result<[u8; 32], LightClientError> {
+    hash_tree_root_pubkeys::<H>(
+        sync_committee.pubkeys.iter().map(|pubkey| &pubkey.0),
+        &sync_committee.aggregate_pubkey.0,
+    )
+}
 
-        return false;
+// Root of a `SyncCommittee` given as its members' and aggregate pubkeys.
+pub(super) fn hash_tree_root_pubkeys<'p, H: Hasher>(
+    pubkeys: impl ExactSizeIterator<Item = &'p [u8; PUBKEY_SIZE]>,
+    aggregate_pubkey: &[u8; PUBKEY_SIZE],
+) -> Result<[u8; 32], LightClientError> {
+    if pubkeys.len() != SYNC_COMMITTEE_SIZE {
+        return Err(LightClientError::HashTreeRoot);
     }
-    for i in 0..depth {
-        if branch[i as usize].as_bytes().len() < 32 as usize {
-            // log::error!(target: "ethereum-beacon-client", "Merkle proof branch not 32 bytes.");
+    let pubkeys = merkleize::<H>(pubkeys.map(hash_tree_root_pubkey::<H>));
+    let aggregate_pubkey = hash_tree_root_pubkey::<H>(aggregate_pubkey);
 
-            return false;
-        }
-        if (index / (2u32.pow(i as u32) as u64) % 2) == 0 {
-            // left node
-            let mut data = [0u8; 64];
-            data[0..32].copy_from_slice(&(value.0));
-            data[32..64].copy_from_slice(&(branch[i as usize].0));
-            value = sha2_256(&data).into();
-        } else {
-            let mut data = [0u8; 64]; // right node
-            data[0..32].copy_from_slice(&(branch[i as usize].0));
-            data[32..64].copy_from_slice(&(value.0));
-            value = sha2_256(&data).into();
+    Ok(H::hash_pair(&pubkeys, &aggregate_pubkey))
+}
+
+fn hash_tree_root_pubkey<H: Hasher>(pubkey: &[u8; PUBKEY_SIZE]) -> [u8; 32] {
+    let mut chunks = [0u8; 64];
+    chunks[..PUBKEY_SIZE].copy_from_slice(pubkey);
+    H::hash(&chunks)
+}
+
+fn u64_chunk(value: u64) -> [u8; 32] {
+    let mut chunk = [0u8; 32];
+    chunk[..8].copy_from_slice(&value.to_le_bytes());
+    chunk
+}
+
+// Merkle root of `chunks` padded with zero chunks to the next power of two. The chunks are
+// streamed through one pending subtree per level, like the carries of a binary counter, so a
+// committee is merkleized without collecting its leaves first. Instead of padding the leaves, the
+// missing right subtrees are filled in with the root of a zero subtree.
+fn merkleize<H: Hasher>(chunks: impl IntoIterator<Item = [u8; 32]>) -> [u8; 32] {
+    let mut pending = [[0u8; 32]; 64];
+    let mut count = 0u64;
+    for chunk in chunks {
+        let mut node = chunk;
+        let mut level = 0;
+        while (count >> level) & 1 == 1 {
+            node = H::hash_pair(&pending[level], &node);
+            level += 1;
         }
+        pending[level] = node;
+        count += 1;
+    }
+    if count == 0 {
+        return [0u8; 32];
+    }
+
+    // Complete the tree to the next power of two with zero subtrees.
+    let depth = 64 - (count - 1).leading_zeros() as usize;
+    let mut zero = [0u8; 32];
+    let mut root: Option<[u8; 32]> = None;
+    for level in 0..depth {
+        root = match ((count >> level) & 1 == 1, root) {
+            (true, None) => Some(H::hash_pair(&pending[level], &zero)),
+            (true, Some(node)) => Some(H::hash_pair(&pending[level], &node)),
+            (false, Some(node)) => Some(H::hash_pair(&node, &zero)),
+            (false, None) => None,
+        };
+        zero = H::hash_pair(&zero, &zero);
     }
+    root.unwrap_or(pending[depth])
+}
 
-    return value == root;
+pub(super) fn verify_signed_header<V: BlsVerifier, H: Hasher>(
+    sync_committee_bits: Vec<u8>,
+    sync_committee_signature: Vec<u8>,
+    sync_committee: &PreparedSyncCommittee<V>,
+    signature_slot: u64,
+    header: BeaconHeader,
+    spec: &ChainSpec<H>,
+) -> Result<(), LightClientError> {
+    // Aggregates the pubkeys of the sync committee members that participated in siging the header.
+    let participant_pubkey = sync_committee.aggregate_participants(&sync_committee_bits)?;
+
+    verify_sync_aggregate_signature::<V, H>(
+        &participant_pubkey,
+        &sync_committee_signature,
+        signature_slot,
+        header,
+        spec,
+    )
 }
 
-pub(super) fn compute_domain(
+/// Verifies the sync aggregate signature over `header` by the already aggregated participants.
+pub(super) fn verify_sync_aggregate_signature<V: BlsVerifier, H: Hasher>(
+    participant_pubkey: &V::AggregatePublicKey,
+    sync_committee_signature: &[u8],
+    signature_slot: u64,
+    header: BeaconHeader,
+    spec: &ChainSpec<H>,
+) -> Result<(), LightClientError> {
+    let domain_type = DOMAIN_SYNC_COMMITTEE.to_vec();
+    let fork_version = compute_signature_fork_version(spec, signature_slot);
+    // Domains are used for seeds, for signatures, and for selecting aggregators.
+    let domain = compute_domain::<H>(domain_type, fork_version, spec.genesis_validators_root)?;
+    // Hash tree root of SigningData - object root + domain
+    let signing_root = compute_signing_root::<H>(header, domain)?;
+
+    // Verify sync committee aggregate signature.
+    V::fast_aggregate_verify(
+        participant_pubkey,
+        signing_root.as_bytes(),
+        sync_committee_signature,
+    )?;
+
+    Ok(())
+}
+
+/// The fork version of the domain of a sync committee signature made at `signature_slot`. The
+/// signature is produced over the block of the slot before, the fork active at that slot decides
+/// the domain. Genesis has no slot before it and signs with the version of its own fork.
+pub fn compute_signature_fork_version<H>(spec: &ChainSpec<H>, signature_slot: u64) -> ForkVersion {
+    spec.fork_version_at_slot(signature_slot.max(1) - 1)
+}
+
+pub fn compute_domain<H: Hasher>(
     domain_type: Vec<u8>,
-    fork_version: Option<ForkVersion>,
+    fork_version: ForkVersion,
     genesis_validators_root: Root,
-) -> Result<Domain, String> {
-    let unwrapped_fork_version: ForkVersion;
-    if fork_version.is_none() {
-        unwrapped_fork_version = GENESIS_FORK_VERSION;
-    } else {
-        unwrapped_fork_version = fork_version.unwrap();
-    }
-
-    let fork_data_root = compute_fork_data_root(unwrapped_fork_version, genesis_validators_root)?;
+) -> Result<Domain, LightClientError> {
+    let fork_data_root = compute_fork_data_root::<H>(fork_version, genesis_validators_root)?;
 
     let mut domain = [0u8; 32];
     domain[0..4].copy_from_slice(&(domain_type));
@@ -174,84 +379,48 @@ pub(super) fn compute_domain(
     Ok(domain.into())
 }
 
-pub(super)fn compute_fork_data_root(
+pub(super) fn compute_fork_data_root<H: Hasher>(
     current_version: ForkVersion,
     genesis_validators_root: Root,
-) -> Result<Root, String> {
-    let hash_root = hash_tree_root_fork_data(ForkData {
+) -> Result<Root, LightClientError> {
+    let hash_root = hash_tree_root_fork_data::<H>(ForkData {
         current_version,
         genesis_validators_root: genesis_validators_root.into(),
-    })
-    .map_err(|_| "Fork data hash tree root failed")?;
+    })?;
 
     Ok(hash_root.into())
 }
 
-pub(super)fn hash_tree_root_fork_data(fork_data: ForkData) -> Result<[u8; 32], String> {
-    hash_tree_root(SSZForkData {
-        current_version: fork_data.current_version,
-        genesis_validators_root: fork_data.genesis_validators_root,
-    })
+pub(super) fn hash_tree_root_fork_data<H: Hasher>(
+    fork_data: ForkData,
+) -> Result<[u8; 32], LightClientError> {
+    let mut current_version = [0u8; 32];
+    current_version[..4].copy_from_slice(&fork_data.current_version);
+    Ok(H::hash_pair(
+        &current_version,
+        &fork_data.genesis_validators_root,
+    ))
 }
 
-pub(super)fn bls_fast_aggregate_verify(
-    pubkeys: Vec<PublicKey>,
-    message: H256,
-    signature: Vec<u8>,
-) -> Result<(), String> {
-    let sig = Signature::from_bytes(&signature[..]);
-    if let Err(_e) = sig {
-        return Err("InvalidSignature".into());
-    }
-
-    let agg_sig = AggregateSignature::from_signature(&sig.unwrap());
-
-    let public_keys_res: Result<Vec<milagro_bls::PublicKey>, _> = pubkeys
-        .iter()
-        .map(|bytes| milagro_bls::PublicKey::from_bytes_unchecked(&bytes.0))
-        .collect();
-    if let Err(e) = public_keys_res {
-        match e {
-            AmclError::InvalidPoint => return Err("InvalidSignaturePoint".into()),
-            _ => return Err("InvalidSignature".into()),
-        };
-    }
+pub fn compute_signing_root<H: Hasher>(
+    beacon_header: BeaconHeader,
+    domain: Domain,
+) -> Result<Root, LightClientError> {
+    let header_hash_tree_root = beacon_header.hash_tree_root_with::<H>();
 
-    let agg_pub_key_res = AggregatePublicKey::into_aggregate(&public_keys_res.unwrap());
-    if let Err(_e) = agg_pub_key_res {
-        // log::error!(target: "ethereum-beacon-client", "invalid public keys: {:?}.", e);
-        return Err("InvalidAggregatePublicKeys".into());
-    }
-
-    if agg_sig.fast_aggregate_verify_pre_aggregated(&message.as_bytes(), &agg_pub_key_res.unwrap())
-    {
-        Ok(())
-    } else {
-        Err("SignatureVerificationFailed".into())
-    }
-}
-fn compute_signing_root(beacon_header: BeaconHeader, domain: Domain) -> Result<Root, String> {
-    let beacon_header_root = hash_tree_root_beacon_header(beacon_header)
-        .map_err(|_| "Beacon header hash tree root failed")?;
-
-    let header_hash_tree_root: H256 = beacon_header_root.into();
-
-    let hash_root = hash_tree_root_signing_data(SigningData {
+    let hash_root = hash_tree_root_signing_data::<H>(SigningData {
         object_root: header_hash_tree_root,
         domain,
-    })
-    .map_err(|_| "Signing root hash tree root failed")?;
+    })?;
 
     Ok(hash_root.into())
 }
 
-
-
-
-fn hash_tree_root_signing_data(signing_data: SigningData) -> Result<[u8; 32], String> {
-    hash_tree_root(SSZSigningData {
-        object_root: signing_data.object_root.into(),
-        domain: signing_data.domain.into(),
-    })
+fn hash_tree_root_signing_data<H: Hasher>(
+    signing_data: SigningData,
+) -> Result<[u8; 32], LightClientError> {
+    Ok(H::hash_pair(
+        &signing_data.object_root.0,
+        &signing_data.domain.0,
+    ))
 }
-
@@ -0,0 +1,242 @@
+//! Borrowed views over SSZ encoded updates.
+//!
+//! Decoding a `SyncCommitteePeriodUpdate` copies the whole next sync committee, about 24KiB on
+//! mainnet, into freshly allocated vectors and again whenever a verification step takes it by
+//! value. The views here only record where each field starts in the encoded bytes, the committee,
+//! branches and sync aggregate are read straight out of them. Only the small headers are decoded.
+
+use super::types::*;
+use super::utils::*;
+use crate::bls::BlsVerifier;
+use crate::chain_spec::{ChainSpec, ForkName};
+use crate::error::{BlsError, LightClientError};
+use crate::merkle::get_generalized_index_length;
+use crate::hasher::{DefaultHasher, Hasher};
+
+/// An SSZ encoded `SyncCommittee`.
+#[derive(Clone, Copy, Debug)]
+pub struct SyncCommitteeView<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> SyncCommitteeView<'a> {
+    pub const SSZ_SIZE: usize = (SYNC_COMMITTEE_SIZE + 1) * PUBKEY_SIZE;
+
+    pub fn new(bytes: &'a [u8]) -> Result<Self, LightClientError> {
+        if bytes.len() != Self::SSZ_SIZE {
+            return Err(LightClientError::Decode {
+                field: "sync_committee",
+            });
+        }
+        Ok(SyncCommitteeView { bytes })
+    }
+
+    pub fn pubkeys(&self) -> impl ExactSizeIterator<Item = &'a [u8; PUBKEY_SIZE]> {
+        self.bytes[..SYNC_COMMITTEE_SIZE * PUBKEY_SIZE]
+            .chunks_exact(PUBKEY_SIZE)
+            .map(|pubkey| pubkey.try_into().unwrap())
+    }
+
+    pub fn pubkey(&self, index: usize) -> Option<&'a [u8; PUBKEY_SIZE]> {
+        if index >= SYNC_COMMITTEE_SIZE {
+            return None;
+        }
+        self.bytes[index * PUBKEY_SIZE..(index + 1) * PUBKEY_SIZE].try_into().ok()
+    }
+
+    pub fn aggregate_pubkey(&self) -> &'a [u8; PUBKEY_SIZE] {
+        self.bytes[SYNC_COMMITTEE_SIZE * PUBKEY_SIZE..].try_into().unwrap()
+    }
+
+    pub fn hash_tree_root(&self) -> Result<[u8; 32], LightClientError> {
+        self.hash_tree_root_with::<DefaultHasher>()
+    }
+
+    pub fn hash_tree_root_with<H: Hasher>(&self) -> Result<[u8; 32], LightClientError> {
+        hash_tree_root_pubkeys::<H>(self.pubkeys(), self.aggregate_pubkey())
+    }
+
+    /// Aggregates the pubkeys of the members whose bit is set in the packed `sync_committee_bits`,
+    /// decompressing only the points the aggregation needs.
+    pub(crate) fn aggregate_participants<V: BlsVerifier>(
+        &self,
+        sync_committee_bits: &[u8],
+    ) -> Result<V::AggregatePublicKey, LightClientError> {
+        crate::bls::aggregate_participants::<V>(
+            SYNC_COMMITTEE_SIZE,
+            |index| is_bit_set(sync_committee_bits, index),
+            |index| {
+                let pubkey = self.pubkey(index).ok_or(BlsError::InvalidPublicKey)?;
+                Ok(V::decompress(&PublicKey(*pubkey))?)
+            },
+            || Ok(V::decompress(&PublicKey(*self.aggregate_pubkey()))?),
+        )
+    }
+}
+
+impl From<SyncCommitteeView<'_>> for SyncCommittee {
+    fn from(value: SyncCommitteeView<'_>) -> Self {
+        SyncCommittee {
+            pubkeys: value.pubkeys().map(|pubkey| PublicKey(*pubkey)).collect(),
+            aggregate_pubkey: PublicKey(*value.aggregate_pubkey()),
+        }
+    }
+}
+
+/// An SSZ encoded `LightClientUpdate` of any supported fork, the counterpart of
+/// [`SyncCommitteePeriodUpdate::decode`].
+#[derive(Clone, Copy, Debug)]
+pub struct SyncCommitteePeriodUpdateView<'a> {
+    fork: ForkName,
+    attested_header: &'a [u8],
+    next_sync_committee: SyncCommitteeView<'a>,
+    next_sync_committee_branch: &'a [u8],
+    finalized_header: &'a [u8],
+    finality_branch: &'a [u8],
+    sync_committee_bits: &'a [u8],
+    sync_committee_signature: &'a [u8],
+    signature_slot: u64,
+}
+
+impl<'a> SyncCommitteePeriodUpdateView<'a> {
+    /// Splits `bytes` into its fields using the container of the fork its attested header belongs
+    /// to. Only lengths and offsets are checked, the fields are validated during verification.
+    pub fn new<H: Hasher>(bytes: &'a [u8], spec: &ChainSpec<H>) -> Result<Self, LightClientError> {
+        let fork = fork_of_encoded_header(bytes, HeaderContainer::Update, spec)?;
+        let (next_sync_committee_gindex, finality_gindex) = if fork >= ForkName::Electra {
+            (NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA, FINALIZED_ROOT_GINDEX_ELECTRA)
+        } else {
+            (NEXT_SYNC_COMMITTEE_GINDEX, FINALIZED_ROOT_GINDEX)
+        };
+        let next_sync_committee_depth = get_generalized_index_length(next_sync_committee_gindex);
+        let finality_depth = get_generalized_index_length(finality_gindex);
+        // Before Capella the headers are plain beacon headers stored inline, later they are
+        // variable sized and only their offsets are part of the fixed size prefix.
+        let header_size = if fork < ForkName::Capella {
+            BEACON_HEADER_SIZE
+        } else {
+            OFFSET_SIZE
+        };
+
+        let mut fields = Fields { bytes, position: 0 };
+        let attested_header = fields.next(header_size)?;
+        let next_sync_committee = SyncCommitteeView::new(fields.next(SyncCommitteeView::SSZ_SIZE)?)?;
+        let next_sync_committee_branch = fields.next(next_sync_committee_depth as usize * 32)?;
+        let finalized_header = fields.next(header_size)?;
+        let finality_branch = fields.next(finality_depth as usize * 32)?;
+        let sync_committee_bits = fields.next(SYNC_COMMITTEE_BITS_SIZE)?;
+        let sync_committee_signature = fields.next(SIGNATURE_SIZE)?;
+        let signature_slot = read_u64(fields.next(8)?, 0)?;
+        let fixed_size = fields.position;
+
+        let (attested_header, finalized_header) = if fork < ForkName::Capella {
+            if bytes.len() != fixed_size {
+                return Err(LightClientError::Decode { field: "update" });
+            }
+            (attested_header, finalized_header)
+        } else {
+            let attested_offset = u32::from_le_bytes(read_bytes(attested_header, 0)?) as usize;
+            let finalized_offset = u32::from_le_bytes(read_bytes(finalized_header, 0)?) as usize;
+            if attested_offset != fixed_size
+                || finalized_offset < attested_offset
+                || finalized_offset > bytes.len()
+            {
+                return Err(LightClientError::Decode { field: "update" });
+            }
+            (
+                &bytes[attested_offset..finalized_offset],
+                &bytes[finalized_offset..],
+            )
+        };
+
+        Ok(SyncCommitteePeriodUpdateView {
+            fork,
+            attested_header,
+            next_sync_committee,
+            next_sync_committee_branch,
+            finalized_header,
+            finality_branch,
+            sync_committee_bits,
+            sync_committee_signature,
+            signature_slot,
+        })
+    }
+
+    pub fn fork(&self) -> ForkName {
+        self.fork
+    }
+
+    pub fn attested_header(&self) -> Result<LightClientHeader, LightClientError> {
+        decode_header(self.attested_header, self.fork)
+    }
+
+    pub fn next_sync_committee(&self) -> SyncCommitteeView<'a> {
+        self.next_sync_committee
+    }
+
+    pub fn next_sync_committee_branch(&self) -> impl ExactSizeIterator<Item = H256> + 'a {
+        branch_nodes(self.next_sync_committee_branch)
+    }
+
+    pub fn finalized_header(&self) -> Result<LightClientHeader, LightClientError> {
+        decode_header(self.finalized_header, self.fork)
+    }
+
+    pub fn finality_branch(&self) -> impl ExactSizeIterator<Item = H256> + 'a {
+        branch_nodes(self.finality_branch)
+    }
+
+    /// The participation bits as packed by SSZ, bit `i % 8` of byte `i / 8` is member `i`.
+    pub fn sync_committee_bits(&self) -> &'a [u8] {
+        self.sync_committee_bits
+    }
+
+    pub fn sync_committee_signature(&self) -> &'a [u8] {
+        self.sync_committee_signature
+    }
+
+    pub fn signature_slot(&self) -> u64 {
+        self.signature_slot
+    }
+}
+
+struct Fields<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Fields<'a> {
+    fn next(&mut self, size: usize) -> Result<&'a [u8], LightClientError> {
+        let field = self
+            .bytes
+            .get(self.position..self.position + size)
+            .ok_or(LightClientError::Decode { field: "update" })?;
+        self.position += size;
+        Ok(field)
+    }
+}
+
+fn decode_header(bytes: &[u8], fork: ForkName) -> Result<LightClientHeader, LightClientError> {
+    match fork {
+        ForkName::Phase0 | ForkName::Altair | ForkName::Bellatrix => {
+            decode_ssz::<SSZBeaconBlockHeader>(bytes, "header").map(LightClientHeader::from)
+        }
+        ForkName::Capella => {
+            decode_ssz::<SSZLightClientHeaderCapella>(bytes, "header").map(LightClientHeader::from)
+        }
+        ForkName::Deneb | ForkName::Electra => {
+            decode_ssz::<SSZLightClientHeaderDeneb>(bytes, "header").map(LightClientHeader::from)
+        }
+    }
+}
+
+fn branch_nodes(bytes: &[u8]) -> impl ExactSizeIterator<Item = H256> + '_ {
+    bytes
+        .chunks_exact(32)
+        .map(|node| H256(node.try_into().unwrap()))
+}
+
+fn is_bit_set(bits: &[u8], index: usize) -> bool {
+    bits.get(index / 8)
+        .map_or(false, |byte| (byte >> (index % 8)) & 1 == 1)
+}
@@ -0,0 +1,169 @@
+//! The finalized state is built from its field roots, with `block_roots` and
+//! `historical_summaries` filled in just enough to hold the ancestors under test.
+
+use bacon::merkle::*;
+use bacon::*;
+
+mod common;
+use common::header;
+
+const N: u64 = SLOTS_PER_HISTORICAL_ROOT;
+
+fn spec() -> ChainSpec {
+    ChainSpec::mainnet()
+}
+
+fn capella_slot() -> u64 {
+    spec().capella.epoch * SLOTS_PER_EPOCH
+}
+
+fn hash_pair(left: H256, right: H256) -> H256 {
+    merkle_tree::<DefaultHasher>(&[left, right])[1]
+}
+
+fn u64_chunk(value: u64) -> H256 {
+    let mut chunk = [0u8; 32];
+    chunk[..8].copy_from_slice(&value.to_le_bytes());
+    H256(chunk)
+}
+
+// `block_roots` holding the root of `header` at its slot.
+fn block_roots(header: &LightClientHeader) -> Vec<H256> {
+    let mut roots = vec![H256::default(); N as usize];
+    roots[(header.beacon.slot % N) as usize] = header.beacon.hash_tree_root();
+    roots
+}
+
+struct Fixture {
+    finalized: LightClientHeader,
+    recent: LightClientHeader,
+    recent_proof: AncestryProof,
+    old: LightClientHeader,
+    old_proof: AncestryProof,
+}
+
+fn fixture() -> Fixture {
+    let finalized_slot = capella_slot() + 3 * N + 100;
+    let recent = header(finalized_slot - 10);
+    let old = header(capella_slot() + 5);
+
+    let recent_roots = block_roots(&recent);
+    let old_roots = block_roots(&old);
+    let block_summary_root = merkle_tree::<DefaultHasher>(&old_roots)[1];
+    let state_summary_root = H256([4; 32]);
+
+    // The summary of the first period since Capella, with the rest of the list left empty.
+    let mut summary_branch = vec![state_summary_root];
+    let mut data_root = hash_pair(block_summary_root, state_summary_root);
+    let mut zero = H256::default();
+    for _ in 0..24 {
+        summary_branch.push(zero);
+        data_root = hash_pair(data_root, zero);
+        zero = hash_pair(zero, zero);
+    }
+    let length = u64_chunk(3);
+    summary_branch.push(length);
+    let summaries_root = hash_pair(data_root, length);
+
+    let mut state: Vec<H256> = (0..32).map(|field| H256([field as u8 + 100; 32])).collect();
+    state[5] = merkle_tree::<DefaultHasher>(&recent_roots)[1];
+    state[27] = summaries_root;
+    let state_root = merkle_tree::<DefaultHasher>(&state)[1];
+
+    let mut block_roots_branch =
+        build_proof::<DefaultHasher>(&recent_roots, N + recent.beacon.slot % N).unwrap();
+    block_roots_branch.extend(build_proof::<DefaultHasher>(&state, BLOCK_ROOTS_GINDEX).unwrap());
+    summary_branch
+        .extend(build_proof::<DefaultHasher>(&state, HISTORICAL_SUMMARIES_GINDEX).unwrap());
+
+    let mut finalized = header(finalized_slot);
+    finalized.beacon.state_root = state_root;
+    Fixture {
+        finalized,
+        recent,
+        recent_proof: AncestryProof::BlockRoots { block_roots_branch },
+        old_proof: AncestryProof::HistoricalSummaries {
+            block_roots_branch: build_proof::<DefaultHasher>(&old_roots, N + old.beacon.slot % N)
+                .unwrap(),
+            block_summary_root,
+            historical_summary_branch: summary_branch,
+        },
+        old,
+    }
+}
+
+#[test]
+fn recent_ancestors_are_proven_through_block_roots() {
+    let fixture = fixture();
+    let spec = spec();
+    verify_ancestry(&fixture.finalized, &fixture.recent, &fixture.recent_proof, &spec).unwrap();
+
+    let mut forged = fixture.recent.clone();
+    forged.beacon.proposer_index += 1;
+    assert_eq!(
+        verify_ancestry(&fixture.finalized, &forged, &fixture.recent_proof, &spec).unwrap_err(),
+        LightClientError::InvalidMerkleBranch(MerkleBranch::BlockRoots)
+    );
+    // The same entry of `block_roots` held the root of a block one period earlier.
+    let mut expired = fixture.recent.clone();
+    expired.beacon.slot -= N;
+    assert!(matches!(
+        verify_ancestry(&fixture.finalized, &expired, &fixture.recent_proof, &spec),
+        Err(LightClientError::InvalidAncestorSlot { .. })
+    ));
+}
+
+#[test]
+fn older_ancestors_are_proven_through_historical_summaries() {
+    let fixture = fixture();
+    let spec = spec();
+    verify_ancestry(&fixture.finalized, &fixture.old, &fixture.old_proof, &spec).unwrap();
+
+    let mut wrong_summary = fixture.old_proof.clone();
+    if let AncestryProof::HistoricalSummaries {
+        block_summary_root, ..
+    } = &mut wrong_summary
+    {
+        *block_summary_root = H256([5; 32]);
+    }
+    assert_eq!(
+        verify_ancestry(&fixture.finalized, &fixture.old, &wrong_summary, &spec).unwrap_err(),
+        LightClientError::InvalidMerkleBranch(MerkleBranch::BlockRoots)
+    );
+
+    // No summary exists yet for the period of the finalized header, nor for any before Capella.
+    for slot in [fixture.finalized.beacon.slot - 1, capella_slot() - 1] {
+        assert!(matches!(
+            verify_ancestry(&fixture.finalized, &header(slot), &fixture.old_proof, &spec),
+            Err(LightClientError::InvalidAncestorSlot { .. })
+        ));
+    }
+}
+
+#[test]
+fn ancestors_have_to_precede_the_finalized_header() {
+    let fixture = fixture();
+    let spec = spec();
+    let finalized_slot = fixture.finalized.beacon.slot;
+    for proof in [&fixture.recent_proof, &fixture.old_proof] {
+        for slot in [finalized_slot, finalized_slot + 1, u64::MAX] {
+            assert_eq!(
+                verify_ancestry(&fixture.finalized, &header(slot), proof, &spec),
+                Err(LightClientError::InvalidAncestorSlot {
+                    finalized_slot,
+                    ancestor_slot: slot,
+                })
+            );
+        }
+    }
+
+    // The period of an ancestor in the last one of the slot range never ends.
+    let finalized = header(u64::MAX);
+    assert_eq!(
+        verify_ancestry(&finalized, &header(u64::MAX - 1), &fixture.old_proof, &spec),
+        Err(LightClientError::InvalidAncestorSlot {
+            finalized_slot: u64::MAX,
+            ancestor_slot: u64::MAX - 1,
+        })
+    );
+}
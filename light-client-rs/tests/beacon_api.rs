@@ -0,0 +1,314 @@
+//! The beacon API's JSON responses decode to the same values as the SSZ encoding of the objects.
+//!
+//! No responses recorded from a beacon node are vendored, the responses are written here in the
+//! shape of `/eth/v1/beacon/light_client/updates` and `/bootstrap`: quoted integers, `0x` hex
+//! byte strings, packed bitvectors and the `{version, data}` envelope around every object.
+#![cfg(feature = "serde")]
+
+use bacon::*;
+use serde_json::{json, Value};
+
+mod common;
+use common::*;
+
+// 2^64 + 7, a base fee that doesn't fit a u64.
+const BASE_FEE: &str = "18446744073709551623";
+const BASE_FEE_LE: [u8; 32] = {
+    let mut value = [0u8; 32];
+    value[0] = 7;
+    value[8] = 1;
+    value
+};
+
+fn spec() -> ChainSpec {
+    ChainSpec::mainnet()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let digits: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("0x{}", digits)
+}
+
+fn header_with(slot: u64, execution: Option<ExecutionPayloadHeader>) -> LightClientHeader {
+    let execution_branch = match execution {
+        Some(_) => (0..4).map(|node| H256([node; 32])).collect(),
+        None => Vec::new(),
+    };
+    LightClientHeader {
+        execution,
+        execution_branch,
+        ..header(slot)
+    }
+}
+
+// The execution payload header of the fork of `slot`, if it has one.
+fn execution_at(slot: u64) -> Option<ExecutionPayloadHeader> {
+    let fork = spec().fork_at_slot(slot);
+    if fork < ForkName::Capella {
+        return None;
+    }
+    let deneb = fork >= ForkName::Deneb;
+    Some(ExecutionPayloadHeader {
+        parent_hash: H256([4; 32]),
+        fee_recipient: [5; 20],
+        logs_bloom: vec![6; BYTES_PER_LOGS_BLOOM],
+        block_number: 7,
+        gas_limit: 30_000_000,
+        timestamp: 1_700_000_000,
+        extra_data: vec![8, 9],
+        base_fee_per_gas: BASE_FEE_LE,
+        blob_gas_used: if deneb { 131072 } else { 0 },
+        excess_blob_gas: if deneb { 3 } else { 0 },
+        ..Default::default()
+    })
+}
+
+fn branch(node: u8, depth: usize) -> Vec<H256> {
+    vec![H256([node; 32]); depth]
+}
+
+fn update_at(attested_slot: u64) -> SyncCommitteePeriodUpdate {
+    let electra = spec().fork_at_slot(attested_slot) >= ForkName::Electra;
+    let finalized_slot = attested_slot - 2 * SLOTS_PER_EPOCH;
+    SyncCommitteePeriodUpdate {
+        attested_header: header_with(attested_slot, execution_at(attested_slot)),
+        next_sync_committee: sync_committee(),
+        next_sync_committee_branch: branch(11, if electra { 6 } else { 5 }),
+        finalized_header: header_with(finalized_slot, execution_at(finalized_slot)),
+        finality_branch: branch(12, if electra { 7 } else { 6 }),
+        sync_aggregate: SyncAggregate {
+            sync_committee_bits: bits(|index| index % 3 == 1),
+            sync_committee_signature: vec![0xbb; SIGNATURE_SIZE],
+        },
+        signature_slot: attested_slot + 1,
+    }
+}
+
+fn bootstrap_at(slot: u64) -> LightClientBootstrap {
+    let electra = spec().fork_at_slot(slot) >= ForkName::Electra;
+    LightClientBootstrap {
+        header: header_with(slot, execution_at(slot)),
+        current_sync_committee: sync_committee(),
+        current_sync_committee_branch: branch(13, if electra { 6 } else { 5 }),
+    }
+}
+
+fn fork_slots() -> Vec<u64> {
+    let spec = spec();
+    [spec.altair, spec.capella, spec.deneb, spec.electra]
+        .iter()
+        .map(|fork| slot_of(*fork) + 2 * SLOTS_PER_EPOCH)
+        .collect()
+}
+
+fn versioned(slot: u64, data: Value) -> Value {
+    let version = format!("{:?}", spec().fork_at_slot(slot)).to_lowercase();
+    json!({ "version": version, "data": data })
+}
+
+fn branch_json(branch: &[H256]) -> Value {
+    branch.iter().map(|node| hex(&node.0)).collect()
+}
+
+fn header_json(header: &LightClientHeader) -> Value {
+    let beacon = &header.beacon;
+    let beacon = json!({
+        "slot": beacon.slot.to_string(),
+        "proposer_index": beacon.proposer_index.to_string(),
+        "parent_root": hex(&beacon.parent_root.0),
+        "state_root": hex(&beacon.state_root.0),
+        "body_root": hex(&beacon.body_root.0),
+    });
+    let execution = match &header.execution {
+        Some(execution) => execution,
+        None => return json!({ "beacon": beacon }),
+    };
+    let mut execution_json = json!({
+        "parent_hash": hex(&execution.parent_hash.0),
+        "fee_recipient": hex(&execution.fee_recipient),
+        "state_root": hex(&execution.state_root.0),
+        "receipts_root": hex(&execution.receipts_root.0),
+        "logs_bloom": hex(&execution.logs_bloom),
+        "prev_randao": hex(&execution.prev_randao.0),
+        "block_number": execution.block_number.to_string(),
+        "gas_limit": execution.gas_limit.to_string(),
+        "gas_used": execution.gas_used.to_string(),
+        "timestamp": execution.timestamp.to_string(),
+        "extra_data": hex(&execution.extra_data),
+        "base_fee_per_gas": BASE_FEE,
+        "block_hash": hex(&execution.block_hash.0),
+        "transactions_root": hex(&execution.transactions_root.0),
+        "withdrawals_root": hex(&execution.withdrawals_root.0),
+    });
+    if spec().fork_at_slot(header.beacon.slot) >= ForkName::Deneb {
+        execution_json["blob_gas_used"] = execution.blob_gas_used.to_string().into();
+        execution_json["excess_blob_gas"] = execution.excess_blob_gas.to_string().into();
+    }
+    json!({
+        "beacon": beacon,
+        "execution": execution_json,
+        "execution_branch": branch_json(&header.execution_branch),
+    })
+}
+
+fn sync_committee_json(committee: &SyncCommittee) -> Value {
+    json!({
+        "pubkeys": committee.pubkeys.iter().map(|pubkey| hex(&pubkey.0)).collect::<Vec<_>>(),
+        "aggregate_pubkey": hex(&committee.aggregate_pubkey.0),
+    })
+}
+
+fn sync_aggregate_json(aggregate: &SyncAggregate) -> Value {
+    let mut bits = vec![0u8; SYNC_COMMITTEE_SIZE / 8];
+    for (index, bit) in aggregate.sync_committee_bits.iter().enumerate() {
+        if bit {
+            bits[index / 8] |= 1 << (index % 8);
+        }
+    }
+    json!({
+        "sync_committee_bits": hex(&bits),
+        "sync_committee_signature": hex(&aggregate.sync_committee_signature),
+    })
+}
+
+fn update_json(update: &SyncCommitteePeriodUpdate) -> Value {
+    versioned(
+        update.attested_header.beacon.slot,
+        json!({
+            "attested_header": header_json(&update.attested_header),
+            "next_sync_committee": sync_committee_json(&update.next_sync_committee),
+            "next_sync_committee_branch": branch_json(&update.next_sync_committee_branch),
+            "finalized_header": header_json(&update.finalized_header),
+            "finality_branch": branch_json(&update.finality_branch),
+            "sync_aggregate": sync_aggregate_json(&update.sync_aggregate),
+            "signature_slot": update.signature_slot.to_string(),
+        }),
+    )
+}
+
+fn bootstrap_json(bootstrap: &LightClientBootstrap) -> Value {
+    versioned(
+        bootstrap.header.beacon.slot,
+        json!({
+            "header": header_json(&bootstrap.header),
+            "current_sync_committee": sync_committee_json(&bootstrap.current_sync_committee),
+            "current_sync_committee_branch": branch_json(&bootstrap.current_sync_committee_branch),
+        }),
+    )
+}
+
+fn assert_same_update(json: &SyncCommitteePeriodUpdate, ssz: &SyncCommitteePeriodUpdate) {
+    assert_eq!(json.attested_header, ssz.attested_header);
+    assert_eq!(json.next_sync_committee, ssz.next_sync_committee);
+    assert_eq!(
+        json.next_sync_committee_branch,
+        ssz.next_sync_committee_branch
+    );
+    assert_eq!(json.finalized_header, ssz.finalized_header);
+    assert_eq!(json.finality_branch, ssz.finality_branch);
+    assert_eq!(json.sync_aggregate, ssz.sync_aggregate);
+    assert_eq!(json.signature_slot, ssz.signature_slot);
+}
+
+#[test]
+fn updates_response_matches_the_ssz_encoding() {
+    let spec = spec();
+    let updates: Vec<_> = fork_slots().into_iter().map(update_at).collect();
+    let response = Value::Array(updates.iter().map(update_json).collect()).to_string();
+
+    let decoded: Vec<Versioned<SyncCommitteePeriodUpdate>> =
+        serde_json::from_str(&response).unwrap();
+    assert_eq!(decoded.len(), updates.len());
+    for (versioned, update) in decoded.iter().zip(&updates) {
+        let bytes = update.to_ssz_bytes(&spec).unwrap();
+        let ssz = SyncCommitteePeriodUpdate::decode(&bytes, &spec).unwrap();
+        assert_eq!(
+            versioned.version,
+            spec.fork_at_slot(update.attested_header.beacon.slot)
+        );
+        assert_same_update(&versioned.data, &ssz);
+        assert_same_update(&versioned.data, update);
+    }
+}
+
+#[test]
+fn bootstrap_response_matches_the_ssz_encoding() {
+    let spec = spec();
+    for slot in fork_slots() {
+        let bootstrap = bootstrap_at(slot);
+        let response = bootstrap_json(&bootstrap).to_string();
+
+        let decoded: Versioned<LightClientBootstrap> = serde_json::from_str(&response).unwrap();
+        let bytes = bootstrap.to_ssz_bytes(&spec).unwrap();
+        let ssz = LightClientBootstrap::decode(&bytes, &spec).unwrap();
+        assert_eq!(decoded.version, spec.fork_at_slot(slot));
+        assert_eq!(decoded.data.header, ssz.header);
+        assert_eq!(
+            decoded.data.current_sync_committee,
+            ssz.current_sync_committee
+        );
+        assert_eq!(
+            decoded.data.current_sync_committee_branch,
+            ssz.current_sync_committee_branch
+        );
+        assert_eq!(decoded.data.header, bootstrap.header);
+    }
+}
+
+#[test]
+fn integers_are_read_quoted_or_plain() {
+    let beacon = |slot: Value| {
+        serde_json::from_value::<BeaconHeader>(json!({
+            "slot": slot,
+            "proposer_index": "3",
+            "parent_root": hex(&[1; 32]),
+            "state_root": hex(&[2; 32]),
+            "body_root": hex(&[3; 32]),
+        }))
+    };
+    assert_eq!(beacon(json!("12345")).unwrap().slot, 12345);
+    assert_eq!(beacon(json!(12345)).unwrap().slot, 12345);
+    assert_eq!(beacon(json!(u64::MAX.to_string())).unwrap().slot, u64::MAX);
+    assert!(beacon(json!("0x3039")).is_err());
+    assert!(beacon(json!("18446744073709551616")).is_err());
+    assert!(beacon(json!("-1")).is_err());
+
+    let slot = slot_of(spec().deneb);
+    let mut execution = header_json(&header_with(slot, execution_at(slot)));
+    let base_fee = |execution: &Value| {
+        serde_json::from_value::<ExecutionPayloadHeader>(execution["execution"].clone())
+            .map(|execution| execution.base_fee_per_gas)
+    };
+    assert_eq!(base_fee(&execution).unwrap(), BASE_FEE_LE);
+    // 2^256 doesn't fit.
+    execution["execution"]["base_fee_per_gas"] =
+        json!("115792089237316195423570985008687907853269984665640564039457584007913129639936");
+    assert!(base_fee(&execution).is_err());
+    execution["execution"]["base_fee_per_gas"] = json!("");
+    assert!(base_fee(&execution).is_err());
+}
+
+#[test]
+fn malformed_hex_and_bitvectors_are_rejected() {
+    let aggregate = |bits: &str| {
+        serde_json::from_value::<SyncAggregate>(json!({
+            "sync_committee_bits": bits,
+            "sync_committee_signature": hex(&[0xbb; SIGNATURE_SIZE]),
+        }))
+    };
+    let all = hex(&[0xff; SYNC_COMMITTEE_SIZE / 8]);
+    let decoded = aggregate(&all).unwrap();
+    assert_eq!(decoded.sync_committee_bits, bits(|_| true));
+    // Upper case digits and a missing prefix are accepted.
+    assert_eq!(aggregate(&all[2..].to_uppercase()).unwrap(), decoded);
+
+    assert!(aggregate(&hex(&[0xff; SYNC_COMMITTEE_SIZE / 8 - 1])).is_err());
+    assert!(aggregate(&hex(&[0xff; SYNC_COMMITTEE_SIZE / 8 + 1])).is_err());
+    assert!(aggregate(&all[..all.len() - 1]).is_err());
+    assert!(aggregate(&all.replace('f', "g")).is_err());
+
+    let root = |root: String| serde_json::from_value::<H256>(Value::String(root));
+    assert_eq!(root(hex(&[9; 32])).unwrap(), H256([9; 32]));
+    assert!(root(hex(&[9; 31])).is_err());
+    assert!(root(hex(&[9; 33])).is_err());
+}
@@ -0,0 +1,214 @@
+//! Both BLS backends have to reach the same verdict on the same updates, otherwise the relayer
+//! (blst) and the on-chain verifier (milagro) could disagree about an update.
+//!
+//! The crate's dev-dependency on itself enables both backends for every test run.
+
+use bacon::utils::{compute_domain, compute_signing_root};
+use bacon::*;
+use blst::min_pk::{AggregatePublicKey, AggregateSignature, SecretKey};
+
+mod common;
+use common::*;
+
+const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+fn spec() -> ChainSpec {
+    altair_spec()
+}
+
+fn secret_keys() -> Vec<SecretKey> {
+    (0..SYNC_COMMITTEE_SIZE)
+        .map(|index| {
+            let mut ikm = [0u8; 32];
+            ikm[..8].copy_from_slice(&(index as u64).to_le_bytes());
+            SecretKey::key_gen(&ikm, &[]).unwrap()
+        })
+        .collect()
+}
+
+fn sync_committee(secret_keys: &[SecretKey]) -> SyncCommittee {
+    let pubkeys: Vec<_> = secret_keys.iter().map(|sk| sk.sk_to_pk()).collect();
+    let aggregate_pubkey =
+        AggregatePublicKey::aggregate(&pubkeys.iter().collect::<Vec<_>>(), false).unwrap();
+    SyncCommittee {
+        pubkeys: pubkeys.iter().map(|pk| PublicKey(pk.compress())).collect(),
+        aggregate_pubkey: PublicKey(aggregate_pubkey.to_public_key().compress()),
+    }
+}
+
+// Signs `header` at `signature_slot` with the members flagged in `signers`.
+fn sign(
+    secret_keys: &[SecretKey],
+    signers: &[bool],
+    header: &LightClientHeader,
+    signature_slot: u64,
+    spec: &ChainSpec,
+) -> Vec<u8> {
+    let domain = compute_domain::<DefaultHasher>(
+        DOMAIN_SYNC_COMMITTEE.to_vec(),
+        spec.fork_version_at_slot(signature_slot - 1),
+        spec.genesis_validators_root,
+    )
+    .unwrap();
+    let signing_root =
+        compute_signing_root::<DefaultHasher>(header.beacon.clone(), domain).unwrap();
+    let signatures: Vec<_> = secret_keys
+        .iter()
+        .zip(signers)
+        .filter(|(_, signer)| **signer)
+        .map(|(sk, _)| sk.sign(signing_root.as_bytes(), DST, &[]))
+        .collect();
+    AggregateSignature::aggregate(&signatures.iter().collect::<Vec<_>>(), false)
+        .unwrap()
+        .to_signature()
+        .compress()
+        .to_vec()
+}
+
+fn update(participants: &[bool], signature: Vec<u8>) -> LightClientOptimisticUpdate {
+    LightClientOptimisticUpdate {
+        attested_header: header(100),
+        sync_aggregate: SyncAggregate {
+            sync_committee_bits: bits(|index| participants[index]),
+            sync_committee_signature: signature,
+        },
+        signature_slot: 101,
+    }
+}
+
+// Returns the verdict of both backends after checking that they agree.
+fn verdict(update: &LightClientOptimisticUpdate, sync_committee: &SyncCommittee) -> bool {
+    let spec = spec();
+    let milagro =
+        PreparedSyncCommittee::<MilagroVerifier>::new(sync_committee.clone()).unwrap();
+    let blst = PreparedSyncCommittee::<BlstVerifier>::new(sync_committee.clone()).unwrap();
+    let policy = VerificationPolicy::spec();
+    let milagro = process_optimistic_update(update.clone(), &milagro, 101, &policy, &spec);
+    let blst = process_optimistic_update(update.clone(), &blst, 101, &policy, &spec);
+    assert_eq!(milagro.is_ok(), blst.is_ok(), "milagro: {:?}, blst: {:?}", milagro, blst);
+    milagro.is_ok()
+}
+
+#[test]
+fn backends_agree_on_full_participation() {
+    let secret_keys = secret_keys();
+    let sync_committee = sync_committee(&secret_keys);
+    let everyone = vec![true; SYNC_COMMITTEE_SIZE];
+    let signature = sign(&secret_keys, &everyone, &header(100), 101, &spec());
+
+    assert!(verdict(&update(&everyone, signature), &sync_committee));
+}
+
+#[test]
+fn backends_agree_on_partial_participation() {
+    let secret_keys = secret_keys();
+    let sync_committee = sync_committee(&secret_keys);
+    // Above half, so the aggregate is built by subtracting the non-participants.
+    let participants: Vec<_> = (0..SYNC_COMMITTEE_SIZE).map(|index| index % 4 != 0).collect();
+    let signature = sign(&secret_keys, &participants, &header(100), 101, &spec());
+
+    assert!(verdict(&update(&participants, signature), &sync_committee));
+}
+
+#[test]
+fn backends_agree_on_missing_signers() {
+    let secret_keys = secret_keys();
+    let sync_committee = sync_committee(&secret_keys);
+    let participants: Vec<_> = (0..SYNC_COMMITTEE_SIZE).map(|index| index % 4 != 0).collect();
+    let signature = sign(&secret_keys, &participants, &header(100), 101, &spec());
+    let everyone = vec![true; SYNC_COMMITTEE_SIZE];
+
+    assert!(!verdict(&update(&everyone, signature), &sync_committee));
+}
+
+#[test]
+fn backends_agree_on_wrong_header() {
+    let secret_keys = secret_keys();
+    let sync_committee = sync_committee(&secret_keys);
+    let everyone = vec![true; SYNC_COMMITTEE_SIZE];
+    let mut signed_header = header(100);
+    signed_header.beacon.state_root = H256([9; 32]);
+    let signature = sign(&secret_keys, &everyone, &signed_header, 101, &spec());
+
+    assert!(!verdict(&update(&everyone, signature), &sync_committee));
+}
+
+// A period update whose sync aggregate claims `participants` and carries the signature of
+// `signers`, and the previous update electing the committee of `secret_keys`.
+fn period_updates(
+    secret_keys: &[SecretKey],
+    participants: &[bool],
+    signers: &[bool],
+) -> (SyncCommitteePeriodUpdate, SyncCommitteePeriodUpdate) {
+    let spec = spec();
+    let prev_update = SyncCommitteePeriodUpdate {
+        next_sync_committee: sync_committee(secret_keys),
+        ..period_update(3 * SLOTS_PER_EPOCH, 0)
+    };
+    let mut update = signed_period_update(&spec, PERIOD + 3 * SLOTS_PER_EPOCH, 0);
+    update.sync_aggregate = SyncAggregate {
+        sync_committee_bits: bits(|index| participants[index]),
+        sync_committee_signature: sign(
+            secret_keys,
+            signers,
+            &update.attested_header,
+            update.signature_slot,
+            &spec,
+        ),
+    };
+    (prev_update, update)
+}
+
+// Same as `verdict` for the decoded and the in place verification of a period update.
+fn period_verdict(
+    prev_update: &SyncCommitteePeriodUpdate,
+    update: &SyncCommitteePeriodUpdate,
+) -> bool {
+    let spec = spec();
+    let policy = VerificationPolicy::spec();
+    let milagro = process_sync_committee_period_update::<MilagroVerifier, _>(
+        prev_update.clone(),
+        update.clone(),
+        &policy,
+        &spec,
+    );
+    let blst = process_sync_committee_period_update::<BlstVerifier, _>(
+        prev_update.clone(),
+        update.clone(),
+        &policy,
+        &spec,
+    );
+    assert_eq!(milagro, blst);
+
+    let prev_update = prev_update.to_ssz_bytes(&spec).unwrap();
+    let prev_update = SyncCommitteePeriodUpdateView::new(&prev_update, &spec).unwrap();
+    let update = update.to_ssz_bytes(&spec).unwrap();
+    let update = SyncCommitteePeriodUpdateView::new(&update, &spec).unwrap();
+    let milagro_view = process_sync_committee_period_update_view::<MilagroVerifier, _>(
+        &prev_update,
+        &update,
+        &policy,
+        &spec,
+    );
+    let blst_view = process_sync_committee_period_update_view::<BlstVerifier, _>(
+        &prev_update,
+        &update,
+        &policy,
+        &spec,
+    );
+    assert_eq!(milagro_view.is_ok(), blst_view.is_ok());
+    assert_eq!(milagro_view.is_ok(), milagro.is_ok());
+    milagro.is_ok()
+}
+
+#[test]
+fn backends_agree_on_period_updates() {
+    let secret_keys = secret_keys();
+    let supermajority: Vec<_> = (0..SYNC_COMMITTEE_SIZE).map(|index| index % 4 != 0).collect();
+    let (prev_update, update) = period_updates(&secret_keys, &supermajority, &supermajority);
+    assert!(period_verdict(&prev_update, &update));
+
+    let everyone = vec![true; SYNC_COMMITTEE_SIZE];
+    let (prev_update, update) = period_updates(&secret_keys, &everyone, &supermajority);
+    assert!(!period_verdict(&prev_update, &update));
+}
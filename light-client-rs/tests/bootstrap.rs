@@ -0,0 +1,132 @@
+//! Everything in a bootstrap is checked against the trusted block root, the committee through the
+//! branch at the gindex of the header's fork.
+
+use bacon::merkle::{compute_merkle_root, get_generalized_index_length};
+use bacon::*;
+
+mod common;
+use common::*;
+
+// A bootstrap of `header` whose state root commits to the committee at `gindex`, and the block
+// root of the header once the state root is in place.
+fn trusted_bootstrap(mut header: LightClientHeader, gindex: u64) -> (H256, LightClientBootstrap) {
+    let branch = vec![H256([9; 32]); get_generalized_index_length(gindex) as usize];
+    header.beacon.state_root = compute_merkle_root::<DefaultHasher>(
+        sync_committee().hash_tree_root().unwrap(),
+        branch.clone(),
+        gindex,
+    )
+    .unwrap();
+    let block_root = header.beacon.hash_tree_root();
+    let bootstrap = LightClientBootstrap {
+        header,
+        current_sync_committee: sync_committee(),
+        current_sync_committee_branch: branch,
+    };
+    (block_root, bootstrap)
+}
+
+fn electra_header(spec: &ChainSpec) -> LightClientHeader {
+    header_committing_to::<DefaultHasher>(
+        slot_of(spec.electra),
+        execution(131072),
+        DENEB_EXECUTION_ROOT,
+    )
+}
+
+#[test]
+fn bootstraps_initialize_the_store() {
+    let spec = altair_spec();
+    let (block_root, bootstrap) =
+        trusted_bootstrap(header(SLOTS_PER_EPOCH), CURRENT_SYNC_COMMITTEE_GINDEX);
+    let store = initialize_from_bootstrap(block_root, bootstrap.clone(), &spec).unwrap();
+    assert_eq!(store.finalized_header, bootstrap.header);
+    assert_eq!(store.optimistic_header, bootstrap.header);
+    assert_eq!(
+        store.current_sync_committee.sync_committee(),
+        &sync_committee()
+    );
+    assert!(store.next_sync_committee.is_none());
+}
+
+#[test]
+fn untrusted_block_roots_are_rejected() {
+    let spec = altair_spec();
+    let (block_root, bootstrap) =
+        trusted_bootstrap(header(SLOTS_PER_EPOCH), CURRENT_SYNC_COMMITTEE_GINDEX);
+    let mut untrusted = block_root;
+    untrusted.0[0] ^= 1;
+    assert_eq!(
+        initialize_from_bootstrap(untrusted, bootstrap, &spec).unwrap_err(),
+        LightClientError::UntrustedBootstrapHeader
+    );
+}
+
+#[test]
+fn committees_have_to_be_proven_by_their_branch() {
+    let spec = altair_spec();
+    let (block_root, bootstrap) =
+        trusted_bootstrap(header(SLOTS_PER_EPOCH), CURRENT_SYNC_COMMITTEE_GINDEX);
+
+    let mut tampered = bootstrap.clone();
+    tampered.current_sync_committee_branch[2] = H256([10; 32]);
+    assert_eq!(
+        initialize_from_bootstrap(block_root, tampered, &spec).unwrap_err(),
+        LightClientError::InvalidMerkleBranch(MerkleBranch::CurrentSyncCommittee)
+    );
+
+    // The branch proves the committee, not just any committee of valid points.
+    let mut other_committee = bootstrap;
+    other_committee.current_sync_committee.aggregate_pubkey = PublicKey([0xc0; PUBKEY_SIZE]);
+    assert_eq!(
+        initialize_from_bootstrap(block_root, other_committee, &spec).unwrap_err(),
+        LightClientError::InvalidMerkleBranch(MerkleBranch::CurrentSyncCommittee)
+    );
+}
+
+#[test]
+fn electra_bootstraps_use_the_deeper_gindex() {
+    let spec = ChainSpec::mainnet();
+    let (block_root, bootstrap) =
+        trusted_bootstrap(electra_header(&spec), CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA);
+    assert_eq!(bootstrap.current_sync_committee_branch.len(), 6);
+    initialize_from_bootstrap(block_root, bootstrap, &spec).unwrap();
+
+    // A branch of the pre-Electra depth doesn't reach the committee of an Electra state.
+    let (block_root, bootstrap) =
+        trusted_bootstrap(electra_header(&spec), CURRENT_SYNC_COMMITTEE_GINDEX);
+    assert_eq!(bootstrap.current_sync_committee_branch.len(), 5);
+    assert_eq!(
+        initialize_from_bootstrap(block_root, bootstrap, &spec).unwrap_err(),
+        LightClientError::InvalidMerkleBranch(MerkleBranch::CurrentSyncCommittee)
+    );
+}
+
+#[test]
+fn bootstraps_served_in_a_later_container_are_cut_back_to_their_fork() {
+    let spec = ChainSpec::mainnet();
+    let deneb_header = header_committing_to::<DefaultHasher>(
+        slot_of(spec.deneb),
+        execution(131072),
+        DENEB_EXECUTION_ROOT,
+    );
+    let (block_root, mut bootstrap) =
+        trusted_bootstrap(deneb_header, CURRENT_SYNC_COMMITTEE_GINDEX);
+    // Served under the Electra fork digest, the branch is padded with a zero node in front.
+    let mut electra_spec = ChainSpec::mainnet();
+    electra_spec.electra.epoch = spec.deneb.epoch;
+    bootstrap.current_sync_committee_branch.insert(0, H256::default());
+    let bytes = bootstrap.to_ssz_bytes(&electra_spec).unwrap();
+
+    assert!(LightClientBootstrap::decode(&bytes, &spec).is_err());
+    let decoded = LightClientBootstrap::decode_in_fork(&bytes, ForkName::Electra, &spec).unwrap();
+    assert_eq!(decoded.current_sync_committee_branch.len(), 5);
+    initialize_from_bootstrap(block_root, decoded, &spec).unwrap();
+
+    bootstrap.current_sync_committee_branch[0] = H256([9; 32]);
+    let bytes = bootstrap.to_ssz_bytes(&electra_spec).unwrap();
+    assert_eq!(
+        LightClientBootstrap::decode_in_fork(&bytes, ForkName::Electra, &spec).unwrap_err(),
+        LightClientError::InvalidMerkleBranch(MerkleBranch::CurrentSyncCommittee)
+    );
+}
@@ -0,0 +1,187 @@
+//! The fork schedule of each network decides the signing domain, the update can't pick it.
+
+use bacon::chain_spec::FAR_FUTURE_EPOCH;
+use bacon::*;
+
+mod common;
+use common::*;
+
+// Bellatrix from the third epoch on, after a stretch of Altair.
+fn spec() -> ChainSpec {
+    let mut spec = altair_spec();
+    spec.bellatrix.epoch = 2;
+    spec
+}
+
+const ATTESTED_SLOT: u64 = 2 * SLOTS_PER_EPOCH + 5;
+
+fn optimistic_update(
+    attested_slot: u64,
+    signature_slot: u64,
+    signature: Vec<u8>,
+) -> LightClientOptimisticUpdate {
+    LightClientOptimisticUpdate {
+        attested_header: header(attested_slot),
+        sync_aggregate: SyncAggregate {
+            sync_committee_bits: bits(|index| index < SUPERMAJORITY),
+            sync_committee_signature: signature,
+        },
+        signature_slot,
+    }
+}
+
+fn process(update: LightClientOptimisticUpdate, spec: &ChainSpec) -> Result<(), LightClientError> {
+    let sync_committee =
+        PreparedSyncCommittee::<DefaultBlsVerifier>::new(sync_committee()).unwrap();
+    let policy = VerificationPolicy::spec();
+    let current_slot = update.signature_slot;
+    process_optimistic_update(update, &sync_committee, current_slot, &policy, spec).map(|_| ())
+}
+
+#[test]
+fn networks_schedule_their_forks() {
+    let mainnet = ChainSpec::mainnet();
+    assert_eq!(mainnet.fork_at_epoch(74239), ForkName::Phase0);
+    assert_eq!(mainnet.fork_at_epoch(74240), ForkName::Altair);
+    assert_eq!(mainnet.fork_at_epoch(194047), ForkName::Bellatrix);
+    assert_eq!(mainnet.fork_at_epoch(194048), ForkName::Capella);
+    assert_eq!(mainnet.fork_at_epoch(269568), ForkName::Deneb);
+    assert_eq!(mainnet.fork_at_epoch(364032), ForkName::Electra);
+    assert_eq!(
+        mainnet.fork_version_at_slot(194048 * SLOTS_PER_EPOCH - 1),
+        [0x02, 0x00, 0x00, 0x00]
+    );
+    assert_eq!(
+        mainnet.fork_version_at_slot(194048 * SLOTS_PER_EPOCH),
+        [0x03, 0x00, 0x00, 0x00]
+    );
+
+    assert_eq!(
+        ChainSpec::sepolia().fork_version_at_epoch(0),
+        [0x90, 0x00, 0x00, 0x69]
+    );
+    assert_eq!(ChainSpec::sepolia().fork_at_epoch(56832), ForkName::Capella);
+    // Holesky started out in Bellatrix.
+    assert_eq!(ChainSpec::holesky().fork_at_epoch(0), ForkName::Bellatrix);
+    assert_eq!(
+        ChainSpec::holesky().fork_version_at_epoch(0),
+        [0x03, 0x01, 0x70, 0x00]
+    );
+
+    let devnet = ChainSpec::devnet(H256([7; 32]), 1_700_000_000);
+    assert_eq!(devnet.fork_at_epoch(0), ForkName::Electra);
+    assert_eq!(devnet.genesis_validators_root, H256([7; 32]));
+    assert_ne!(devnet, ChainSpec::mainnet());
+    assert_eq!(
+        altair_spec().fork_at_epoch(FAR_FUTURE_EPOCH - 1),
+        ForkName::Bellatrix
+    );
+}
+
+#[test]
+fn signatures_are_checked_in_the_domain_of_the_signature_slot() {
+    let spec = spec();
+    let header = header(ATTESTED_SLOT).beacon;
+    let signature = sign_with_version(&header, SUPERMAJORITY, spec.bellatrix.version, &spec);
+    process(
+        optimistic_update(ATTESTED_SLOT, ATTESTED_SLOT + 1, signature),
+        &spec,
+    )
+    .unwrap();
+}
+
+#[test]
+fn signatures_of_another_fork_or_network_are_rejected() {
+    let spec = spec();
+    let header = header(ATTESTED_SLOT).beacon;
+
+    // Signed as if Altair were still active, which the slot rules out.
+    let signature = sign_with_version(&header, SUPERMAJORITY, spec.altair.version, &spec);
+    assert_eq!(
+        process(
+            optimistic_update(ATTESTED_SLOT, ATTESTED_SLOT + 1, signature),
+            &spec
+        ),
+        Err(LightClientError::Bls(BlsError::VerificationFailed))
+    );
+
+    // Signed on a network with another genesis.
+    let other_network = ChainSpec {
+        genesis_validators_root: H256([8; 32]),
+        ..spec
+    };
+    let signature = sign_with_version(
+        &header,
+        SUPERMAJORITY,
+        spec.bellatrix.version,
+        &other_network,
+    );
+    assert_eq!(
+        process(
+            optimistic_update(ATTESTED_SLOT, ATTESTED_SLOT + 1, signature),
+            &spec
+        ),
+        Err(LightClientError::Bls(BlsError::VerificationFailed))
+    );
+}
+
+#[test]
+fn the_first_slot_of_a_fork_signs_in_the_fork_before() {
+    let spec = spec();
+    let first_slot = spec.bellatrix.epoch * SLOTS_PER_EPOCH;
+    let header = header(first_slot - 1).beacon;
+    let update = |version| {
+        let signature = sign_with_version(&header, SUPERMAJORITY, version, &spec);
+        optimistic_update(first_slot - 1, first_slot, signature)
+    };
+
+    // The committee signs the last Altair block, though the signature slot is in Bellatrix.
+    assert_eq!(spec.fork_at_slot(first_slot), ForkName::Bellatrix);
+    assert_eq!(
+        utils::compute_signature_fork_version(&spec, first_slot),
+        spec.altair.version
+    );
+    process(update(spec.altair.version), &spec).unwrap();
+    assert_eq!(
+        process(update(spec.bellatrix.version), &spec),
+        Err(LightClientError::Bls(BlsError::VerificationFailed))
+    );
+
+    // One slot later the signed block is the first of Bellatrix.
+    let signature = sign_with_version(&header, SUPERMAJORITY, spec.bellatrix.version, &spec);
+    process(
+        optimistic_update(first_slot - 1, first_slot + 1, signature),
+        &spec,
+    )
+    .unwrap();
+}
+
+#[test]
+fn signatures_at_genesis_take_the_genesis_domain() {
+    let mut spec = spec();
+    spec.altair.epoch = 1;
+    assert_eq!(
+        utils::compute_signature_fork_version(&spec, 0),
+        spec.genesis_fork_version
+    );
+    assert_eq!(
+        utils::compute_signature_fork_version(&spec, 1),
+        spec.genesis_fork_version
+    );
+    assert_eq!(
+        utils::compute_signature_fork_version(&ChainSpec::devnet(H256([7; 32]), 0), 0),
+        [0x60, 0x00, 0x00, 0x38]
+    );
+
+    // No header precedes the genesis slot, an update signed there is out of order.
+    let signature = sign_with_version(
+        &header(0).beacon,
+        SUPERMAJORITY,
+        spec.genesis_fork_version,
+        &spec,
+    );
+    assert_eq!(
+        process(optimistic_update(0, 0, signature), &spec),
+        Err(LightClientError::InvalidSlotOrder)
+    );
+}
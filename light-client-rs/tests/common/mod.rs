@@ -0,0 +1,298 @@
+//! Builders shared by the integration tests. Slots and committee sizes are derived from the
+//! compiled preset so that the tests run under `minimal` as well as `mainnet`.
+#![allow(dead_code)]
+
+use bacon::chain_spec::FAR_FUTURE_EPOCH;
+use bacon::merkle::{build_proof, compute_merkle_root, get_generalized_index_length, merkle_tree};
+use bacon::utils::{compute_domain, compute_signing_root};
+use bacon::*;
+use blst::min_pk::{AggregatePublicKey, AggregateSignature, SecretKey};
+
+pub const PERIOD: u64 = SLOTS_PER_EPOCH * EPOCHS_PER_SYNC_COMMITTEE_PERIOD;
+pub const SUPERMAJORITY: usize = SYNC_COMMITTEE_SIZE * 2 / 3 + 1;
+
+/// Altair from genesis and no later fork, so headers don't need an execution payload.
+pub fn altair_spec() -> ChainSpec {
+    let mut spec = ChainSpec::devnet(H256([7; 32]), 0);
+    spec.capella.epoch = FAR_FUTURE_EPOCH;
+    spec.deneb.epoch = FAR_FUTURE_EPOCH;
+    spec.electra.epoch = FAR_FUTURE_EPOCH;
+    spec
+}
+
+/// A slot just after the activation of `fork`.
+pub fn slot_of(fork: Fork) -> u64 {
+    fork.epoch * SLOTS_PER_EPOCH + 1
+}
+
+pub fn header(slot: u64) -> LightClientHeader {
+    LightClientHeader {
+        beacon: BeaconHeader {
+            slot,
+            proposer_index: 3,
+            parent_root: H256([1; 32]),
+            state_root: H256([2; 32]),
+            body_root: H256([3; 32]),
+        },
+        execution: None,
+        execution_branch: Vec::new(),
+    }
+}
+
+pub fn from_hex(hex: &str) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (byte, pair) in bytes.iter_mut().zip(hex.as_bytes().chunks_exact(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap();
+    }
+    bytes
+}
+
+pub fn execution(blob_gas_used: u64) -> ExecutionPayloadHeader {
+    ExecutionPayloadHeader {
+        parent_hash: H256([4; 32]),
+        fee_recipient: [5; 20],
+        logs_bloom: vec![6; BYTES_PER_LOGS_BLOOM],
+        block_number: 7,
+        gas_limit: 30_000_000,
+        extra_data: vec![8, 9],
+        base_fee_per_gas: [10; 32],
+        blob_gas_used,
+        ..Default::default()
+    }
+}
+
+/// The roots of `execution(0)` as a Capella container and of `execution(131072)` as a Deneb one,
+/// computed with Python's `hashlib` from the SSZ definitions.
+pub const CAPELLA_EXECUTION_ROOT: &str =
+    "f8cc4be93d85f9da7db50c99c63aa880adb5d67e39991b74af2a6e2fac6a69a4";
+pub const DENEB_EXECUTION_ROOT: &str =
+    "4f90f56cccdea5d9c7bf9470ecbd3f1b1ef7525e76ad8f55c712abd988d479a1";
+
+/// A header whose body root commits to `execution_root`, so that it is only valid if its
+/// execution payload header merkleizes to that root.
+pub fn header_committing_to<H: Hasher>(
+    slot: u64,
+    execution: ExecutionPayloadHeader,
+    execution_root: &str,
+) -> LightClientHeader {
+    let execution_branch: Vec<H256> = (0..4).map(|node| H256([node; 32])).collect();
+    let mut header = LightClientHeader {
+        execution: Some(execution),
+        execution_branch: execution_branch.clone(),
+        ..header(slot)
+    };
+    header.beacon.body_root = compute_merkle_root::<H>(
+        H256(from_hex(execution_root)),
+        execution_branch,
+        EXECUTION_PAYLOAD_GINDEX,
+    )
+    .unwrap();
+    header
+}
+
+/// The compressed generator of G1, the pubkey of the secret key 1.
+pub const G1_GENERATOR: [u8; PUBKEY_SIZE] = [
+    0x97, 0xf1, 0xd3, 0xa7, 0x31, 0x97, 0xd7, 0x94, 0x26, 0x95, 0x63, 0x8c, 0x4f, 0xa9, 0xac, 0x0f,
+    0xc3, 0x68, 0x8c, 0x4f, 0x97, 0x74, 0xb9, 0x05, 0xa1, 0x4e, 0x3a, 0x3f, 0x17, 0x1b, 0xac, 0x58,
+    0x6c, 0x55, 0xe8, 0x3f, 0xf9, 0x7a, 0x1a, 0xef, 0xfb, 0x3a, 0xf0, 0x0a, 0xdb, 0x22, 0xc6, 0xbb,
+];
+
+/// A committee of valid points, the store prepares every committee it learns about. All members
+/// hold the secret key 1, see [`sign`].
+pub fn sync_committee() -> SyncCommittee {
+    let pubkey = blst::min_pk::PublicKey::from_bytes(&G1_GENERATOR).unwrap();
+    let aggregate_pubkey =
+        AggregatePublicKey::aggregate(&vec![&pubkey; SYNC_COMMITTEE_SIZE], false).unwrap();
+    SyncCommittee {
+        pubkeys: vec![PublicKey(G1_GENERATOR); SYNC_COMMITTEE_SIZE],
+        aggregate_pubkey: PublicKey(aggregate_pubkey.to_public_key().compress()),
+    }
+}
+
+/// Sync committee bits with the members for which `participant` holds set.
+pub fn bits(participant: impl Fn(usize) -> bool) -> Bitvector<SYNC_COMMITTEE_SIZE> {
+    let mut bytes = vec![0u8; SYNC_COMMITTEE_SIZE / 8];
+    for index in (0..SYNC_COMMITTEE_SIZE).filter(|index| participant(*index)) {
+        bytes[index / 8] |= 1 << (index % 8);
+    }
+    Bitvector::<SYNC_COMMITTEE_SIZE>::deserialize(&bytes).unwrap()
+}
+
+/// An unsigned aggregate of the first `participants` members.
+pub fn sync_aggregate(participants: usize) -> SyncAggregate {
+    SyncAggregate {
+        sync_committee_bits: bits(|index| index < participants),
+        sync_committee_signature: vec![0; SIGNATURE_SIZE],
+    }
+}
+
+/// A header of the fork of `slot` that passes the light client header checks, with an execution
+/// payload from Capella on.
+pub fn valid_header(spec: &ChainSpec, slot: u64) -> LightClientHeader {
+    match spec.fork_at_slot(slot) {
+        ForkName::Phase0 | ForkName::Altair | ForkName::Bellatrix => header(slot),
+        ForkName::Capella => {
+            header_committing_to::<DefaultHasher>(slot, execution(0), CAPELLA_EXECUTION_ROOT)
+        }
+        ForkName::Deneb | ForkName::Electra => {
+            header_committing_to::<DefaultHasher>(slot, execution(131072), DENEB_EXECUTION_ROOT)
+        }
+    }
+}
+
+const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// The aggregate signature of the first `participants` members of [`sync_committee`] over
+/// `header`, in the signing domain of `fork_version`.
+pub fn sign_with_version(
+    header: &BeaconHeader,
+    participants: usize,
+    fork_version: ForkVersion,
+    spec: &ChainSpec,
+) -> Vec<u8> {
+    if participants == 0 {
+        // Nobody signed, the aggregate is the point at infinity.
+        let mut infinity = vec![0; SIGNATURE_SIZE];
+        infinity[0] = 0xc0;
+        return infinity;
+    }
+    let domain = compute_domain::<DefaultHasher>(
+        DOMAIN_SYNC_COMMITTEE.to_vec(),
+        fork_version,
+        spec.genesis_validators_root,
+    )
+    .unwrap();
+    let signing_root = compute_signing_root::<DefaultHasher>(header.clone(), domain).unwrap();
+    let mut secret_key = [0u8; 32];
+    secret_key[31] = 1;
+    let signature =
+        SecretKey::from_bytes(&secret_key)
+            .unwrap()
+            .sign(signing_root.as_bytes(), DST, &[]);
+    AggregateSignature::aggregate(&vec![&signature; participants], false)
+        .unwrap()
+        .to_signature()
+        .compress()
+        .to_vec()
+}
+
+/// Same as [`sign_with_version`] with the version of the fork of the slot before
+/// `signature_slot`, the block the committee signs.
+pub fn sign(
+    header: &BeaconHeader,
+    participants: usize,
+    signature_slot: u64,
+    spec: &ChainSpec,
+) -> Vec<u8> {
+    sign_with_version(
+        header,
+        participants,
+        spec.fork_version_at_slot(signature_slot - 1),
+        spec,
+    )
+}
+
+/// The root of a state at `slot` holding `finalized_root` and `next_sync_committee`, with the
+/// finality and next sync committee branches proving them at the gindices of its fork.
+pub fn attested_state(
+    spec: &ChainSpec,
+    slot: u64,
+    finalized_root: H256,
+    next_sync_committee: &SyncCommittee,
+) -> (H256, Vec<H256>, Vec<H256>) {
+    // The committee is a field of the state, the finalized root the right half of the
+    // finalized checkpoint field. Both fields are leaves of the same depth.
+    let (committee_gindex, checkpoint_gindex) = match spec.fork_at_slot(slot) {
+        ForkName::Electra => (
+            NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA,
+            FINALIZED_ROOT_GINDEX_ELECTRA / 2,
+        ),
+        _ => (NEXT_SYNC_COMMITTEE_GINDEX, FINALIZED_ROOT_GINDEX / 2),
+    };
+    let depth = get_generalized_index_length(committee_gindex);
+    let mut leaves = vec![H256::default(); 1 << depth];
+    let epoch = H256([12; 32]);
+    leaves[(committee_gindex - (1 << depth)) as usize] =
+        next_sync_committee.hash_tree_root().unwrap();
+    leaves[(checkpoint_gindex - (1 << depth)) as usize] =
+        H256(DefaultHasher::hash_pair(&epoch.0, &finalized_root.0));
+
+    let state_root = merkle_tree::<DefaultHasher>(&leaves)[1];
+    let mut finality_branch = vec![epoch];
+    finality_branch.extend(build_proof::<DefaultHasher>(&leaves, checkpoint_gindex).unwrap());
+    let next_sync_committee_branch =
+        build_proof::<DefaultHasher>(&leaves, committee_gindex).unwrap();
+    (state_root, finality_branch, next_sync_committee_branch)
+}
+
+/// A period update that verifies: it finalizes a header two epochs before `attested_slot`,
+/// proves [`sync_committee`] as the next committee and is signed in the slot after it by the
+/// first `participants` members.
+pub fn signed_period_update(
+    spec: &ChainSpec,
+    attested_slot: u64,
+    participants: usize,
+) -> SyncCommitteePeriodUpdate {
+    let finalized_header = valid_header(spec, attested_slot - 2 * SLOTS_PER_EPOCH);
+    let (state_root, finality_branch, next_sync_committee_branch) = attested_state(
+        spec,
+        attested_slot,
+        finalized_header.beacon.hash_tree_root(),
+        &sync_committee(),
+    );
+    let mut attested_header = valid_header(spec, attested_slot);
+    attested_header.beacon.state_root = state_root;
+    let update = SyncCommitteePeriodUpdate {
+        attested_header,
+        next_sync_committee: sync_committee(),
+        next_sync_committee_branch,
+        finalized_header,
+        finality_branch,
+        sync_aggregate: sync_aggregate(participants),
+        signature_slot: attested_slot + 1,
+    };
+    signed(update, participants, spec)
+}
+
+/// `update` signed again by the first `participants` members, after its headers or signature
+/// slot changed.
+pub fn signed(
+    mut update: SyncCommitteePeriodUpdate,
+    participants: usize,
+    spec: &ChainSpec,
+) -> SyncCommitteePeriodUpdate {
+    update.sync_aggregate = SyncAggregate {
+        sync_committee_bits: bits(|index| index < participants),
+        sync_committee_signature: sign(
+            &update.attested_header.beacon,
+            participants,
+            update.signature_slot,
+            spec,
+        ),
+    };
+    update
+}
+
+/// A pre-Electra update finalizing a header two epochs before `attested_slot` and carrying the
+/// next sync committee of its period, signed in the slot after it.
+pub fn period_update(attested_slot: u64, participants: usize) -> SyncCommitteePeriodUpdate {
+    let branch = |gindex| vec![H256([11; 32]); get_generalized_index_length(gindex) as usize];
+    SyncCommitteePeriodUpdate {
+        attested_header: header(attested_slot),
+        next_sync_committee: sync_committee(),
+        next_sync_committee_branch: branch(NEXT_SYNC_COMMITTEE_GINDEX),
+        finalized_header: header(attested_slot - 2 * SLOTS_PER_EPOCH),
+        finality_branch: branch(FINALIZED_ROOT_GINDEX),
+        sync_aggregate: sync_aggregate(participants),
+        signature_slot: attested_slot + 1,
+    }
+}
+
+/// `update` as a node serves it, encoded and decoded again. Headers from before Capella in a
+/// newer container come back with the container's empty execution payload.
+pub fn through_ssz(
+    update: &SyncCommitteePeriodUpdate,
+    spec: &ChainSpec,
+) -> SyncCommitteePeriodUpdate {
+    SyncCommitteePeriodUpdate::decode(&update.to_ssz_bytes(spec).unwrap(), spec).unwrap()
+}
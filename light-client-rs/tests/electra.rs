@@ -0,0 +1,208 @@
+//! Electra moved the finalized checkpoint and the sync committees deeper into the state, the
+//! branches proving them are one node longer from its first slot on.
+
+use bacon::*;
+
+mod common;
+use common::*;
+
+// Deneb for the first two periods, Electra from the third on.
+fn spec() -> ChainSpec {
+    let mut spec = ChainSpec::devnet(H256([7; 32]), 0);
+    spec.electra.epoch = 2 * EPOCHS_PER_SYNC_COMMITTEE_PERIOD;
+    spec
+}
+
+const ELECTRA_SLOT: u64 = 2 * PERIOD + 5;
+
+// Only the slots and the next sync committee of the previous update are used.
+fn checkpoint() -> SyncCommitteePeriodUpdate {
+    period_update(3 * SLOTS_PER_EPOCH, 0)
+}
+
+fn finality_update(update: SyncCommitteePeriodUpdate) -> FinalizedHeaderUpdate {
+    FinalizedHeaderUpdate {
+        attested_header: update.attested_header,
+        finalized_header: update.finalized_header,
+        finality_branch: update.finality_branch,
+        sync_aggregate: update.sync_aggregate,
+        signature_slot: update.signature_slot,
+    }
+}
+
+fn process_finality(
+    update: SyncCommitteePeriodUpdate,
+) -> Result<LightClientHeader, LightClientError> {
+    let sync_committee =
+        PreparedSyncCommittee::<DefaultBlsVerifier>::new(sync_committee()).unwrap();
+    let signature_slot = update.signature_slot;
+    process_finalized_header(
+        finality_update(update),
+        &sync_committee,
+        signature_slot,
+        &VerificationPolicy::spec(),
+        &spec(),
+    )
+}
+
+#[test]
+fn branches_grow_at_the_electra_fork() {
+    let spec = spec();
+    let deneb = signed_period_update(&spec, PERIOD + 3 * SLOTS_PER_EPOCH, SUPERMAJORITY);
+    let electra = signed_period_update(&spec, ELECTRA_SLOT, SUPERMAJORITY);
+    assert_eq!(
+        deneb.next_sync_committee_branch.len() as u64,
+        NEXT_SYNC_COMMITTEE_DEPTH
+    );
+    assert_eq!(deneb.finality_branch.len() as u64, FINALIZED_ROOT_DEPTH);
+    assert_eq!(
+        electra.next_sync_committee_branch.len() as u64,
+        NEXT_SYNC_COMMITTEE_DEPTH_ELECTRA
+    );
+    assert_eq!(
+        electra.finality_branch.len() as u64,
+        FINALIZED_ROOT_DEPTH_ELECTRA
+    );
+
+    // Both containers come from a node, the Electra one decodes with the longer branches.
+    let updates = vec![through_ssz(&deneb, &spec), through_ssz(&electra, &spec)];
+    assert_eq!(
+        updates[1].next_sync_committee_branch,
+        electra.next_sync_committee_branch
+    );
+    assert_eq!(updates[1].finality_branch, electra.finality_branch);
+    let policy = VerificationPolicy::spec();
+    assert_eq!(
+        process_sync_committee_update_chain::<DefaultBlsVerifier, _>(
+            checkpoint(),
+            updates,
+            &policy,
+            &spec
+        ),
+        Ok((sync_committee(), electra.finalized_header))
+    );
+}
+
+#[test]
+fn electra_updates_with_pre_electra_branches_are_rejected() {
+    let spec = spec();
+    let policy = VerificationPolicy::spec();
+    let prev_update = signed_period_update(&spec, PERIOD + 3 * SLOTS_PER_EPOCH, SUPERMAJORITY);
+    let update = signed_period_update(&spec, ELECTRA_SLOT, SUPERMAJORITY);
+    let pre_electra = |branch: &[H256]| branch[1..].to_vec();
+
+    let short_committee_branch = SyncCommitteePeriodUpdate {
+        next_sync_committee_branch: pre_electra(&update.next_sync_committee_branch),
+        ..update.clone()
+    };
+    assert_eq!(
+        process_sync_committee_period_update::<DefaultBlsVerifier, _>(
+            prev_update.clone(),
+            short_committee_branch,
+            &policy,
+            &spec
+        ),
+        Err(LightClientError::InvalidMerkleBranch(
+            MerkleBranch::NextSyncCommittee
+        ))
+    );
+
+    let short_finality_branch = SyncCommitteePeriodUpdate {
+        finality_branch: pre_electra(&update.finality_branch),
+        ..update
+    };
+    assert_eq!(
+        process_sync_committee_period_update::<DefaultBlsVerifier, _>(
+            prev_update,
+            short_finality_branch,
+            &policy,
+            &spec
+        ),
+        Err(LightClientError::InvalidMerkleBranch(
+            MerkleBranch::Finality
+        ))
+    );
+}
+
+#[test]
+fn finality_updates_follow_the_fork_of_the_attested_header() {
+    let spec = spec();
+    let update = signed_period_update(&spec, ELECTRA_SLOT, SUPERMAJORITY);
+    let finalized_header = update.finalized_header.clone();
+    assert_eq!(process_finality(update.clone()), Ok(finalized_header));
+
+    let deneb = signed_period_update(&spec, PERIOD + 3 * SLOTS_PER_EPOCH, SUPERMAJORITY);
+    let finalized_header = deneb.finalized_header.clone();
+    assert_eq!(process_finality(deneb), Ok(finalized_header));
+
+    let pre_electra = SyncCommitteePeriodUpdate {
+        finality_branch: update.finality_branch[1..].to_vec(),
+        ..update
+    };
+    assert_eq!(
+        process_finality(pre_electra),
+        Err(LightClientError::InvalidMerkleBranch(
+            MerkleBranch::Finality
+        ))
+    );
+}
+
+// Electra from genesis, the containers a node serves Deneb updates in once Electra is live.
+fn electra_spec() -> ChainSpec {
+    ChainSpec::devnet(H256([7; 32]), 0)
+}
+
+// The branches of `update` as `normalize_merkle_branch` pads them for an Electra container.
+fn normalized(mut update: SyncCommitteePeriodUpdate) -> SyncCommitteePeriodUpdate {
+    update.next_sync_committee_branch.insert(0, H256::default());
+    update.finality_branch.insert(0, H256::default());
+    update
+}
+
+#[test]
+fn deneb_updates_in_electra_containers_are_cut_back_to_their_fork() {
+    let spec = spec();
+    let deneb = signed_period_update(&spec, PERIOD + 3 * SLOTS_PER_EPOCH, SUPERMAJORITY);
+    let bytes = normalized(deneb.clone()).to_ssz_bytes(&electra_spec()).unwrap();
+
+    // The offsets only fit the Electra container, which the header's slot doesn't lead to.
+    assert!(SyncCommitteePeriodUpdate::decode(&bytes, &spec).is_err());
+    let decoded =
+        SyncCommitteePeriodUpdate::decode_in_fork(&bytes, ForkName::Electra, &spec).unwrap();
+    assert_eq!(
+        decoded.next_sync_committee_branch,
+        deneb.next_sync_committee_branch
+    );
+    assert_eq!(decoded.finality_branch, deneb.finality_branch);
+    assert_eq!(
+        process_sync_committee_period_update::<DefaultBlsVerifier, _>(
+            checkpoint(),
+            decoded,
+            &VerificationPolicy::spec(),
+            &spec
+        ),
+        Ok((sync_committee(), deneb.finalized_header.clone()))
+    );
+
+    let bytes = finality_update(normalized(deneb.clone()))
+        .to_ssz_bytes(&electra_spec())
+        .unwrap();
+    let decoded = FinalizedHeaderUpdate::decode_in_fork(&bytes, ForkName::Electra, &spec).unwrap();
+    assert_eq!(decoded.finality_branch, deneb.finality_branch);
+
+    // Only zero nodes are padding.
+    let mut padded = normalized(deneb.clone());
+    padded.next_sync_committee_branch[0] = H256([13; 32]);
+    let bytes = padded.to_ssz_bytes(&electra_spec()).unwrap();
+    assert_eq!(
+        SyncCommitteePeriodUpdate::decode_in_fork(&bytes, ForkName::Electra, &spec).unwrap_err(),
+        LightClientError::InvalidMerkleBranch(MerkleBranch::NextSyncCommittee)
+    );
+    let mut padded = normalized(deneb);
+    padded.finality_branch[0] = H256([13; 32]);
+    let bytes = finality_update(padded).to_ssz_bytes(&electra_spec()).unwrap();
+    assert_eq!(
+        FinalizedHeaderUpdate::decode_in_fork(&bytes, ForkName::Electra, &spec).err(),
+        Some(LightClientError::InvalidMerkleBranch(MerkleBranch::Finality))
+    );
+}
@@ -0,0 +1,191 @@
+//! The error codes are part of the interface callers outside of Rust branch on, every one of them
+//! is pinned here together with its message.
+
+use bacon::*;
+
+fn cases() -> Vec<(LightClientError, u32, &'static str)> {
+    vec![
+        (
+            LightClientError::Decode { field: "update" },
+            1,
+            "failed to decode update",
+        ),
+        (
+            LightClientError::InvalidLength {
+                expected: 32,
+                actual: 31,
+            },
+            2,
+            "invalid length: expected 32 bytes, got 31",
+        ),
+        (LightClientError::HashTreeRoot, 3, "hash tree root failed"),
+        (
+            LightClientError::Encode { field: "header" },
+            4,
+            "failed to encode header",
+        ),
+        (
+            LightClientError::InvalidMerkleBranch(MerkleBranch::NextSyncCommittee),
+            10,
+            "next sync committee merkle branch is invalid",
+        ),
+        (
+            LightClientError::InvalidMerkleBranch(MerkleBranch::Finality),
+            11,
+            "finality merkle branch is invalid",
+        ),
+        (
+            LightClientError::InvalidMerkleBranch(MerkleBranch::CurrentSyncCommittee),
+            12,
+            "current sync committee merkle branch is invalid",
+        ),
+        (
+            LightClientError::InvalidMerkleBranch(MerkleBranch::Execution),
+            13,
+            "execution payload merkle branch is invalid",
+        ),
+        (
+            LightClientError::InvalidMerkleBranch(MerkleBranch::BlockRoots),
+            14,
+            "block roots merkle branch is invalid",
+        ),
+        (
+            LightClientError::InvalidMerkleBranch(MerkleBranch::HistoricalSummary),
+            15,
+            "historical summary merkle branch is invalid",
+        ),
+        (
+            LightClientError::InsufficientParticipation {
+                participants: 1,
+                required: 342,
+            },
+            20,
+            "sync committee participation too low: 1 of 342 required",
+        ),
+        (
+            LightClientError::Bls(BlsError::InvalidPublicKeyPoint),
+            30,
+            "bls: invalid public key point",
+        ),
+        (
+            LightClientError::Bls(BlsError::InvalidPublicKey),
+            31,
+            "bls: invalid public key",
+        ),
+        (
+            LightClientError::Bls(BlsError::InvalidSignature),
+            32,
+            "bls: invalid signature",
+        ),
+        (
+            LightClientError::Bls(BlsError::InvalidAggregatePublicKeys),
+            33,
+            "bls: invalid aggregate public keys",
+        ),
+        (
+            LightClientError::Bls(BlsError::VerificationFailed),
+            34,
+            "bls: signature verification failed",
+        ),
+        (
+            LightClientError::InvalidSlotOrder,
+            40,
+            "update slots are out of order",
+        ),
+        (
+            LightClientError::InvalidSyncCommitteePeriod {
+                store_period: 3,
+                update_period: 5,
+            },
+            41,
+            "update period 5 can't be verified from store period 3",
+        ),
+        (
+            LightClientError::IrrelevantUpdate,
+            42,
+            "update is not relevant to the store",
+        ),
+        (
+            LightClientError::UnexpectedFinalizedHeader,
+            43,
+            "finalized header present without a finality branch",
+        ),
+        (
+            LightClientError::UnexpectedNextSyncCommittee,
+            44,
+            "next sync committee present without a sync committee branch",
+        ),
+        (
+            LightClientError::NextSyncCommitteeMismatch,
+            45,
+            "next sync committee does not match the known one",
+        ),
+        (
+            LightClientError::InvalidAncestorSlot {
+                finalized_slot: 64,
+                ancestor_slot: 70,
+            },
+            46,
+            "slot 70 can't be proven as an ancestor of slot 64 with this proof",
+        ),
+        (
+            LightClientError::StaleUpdate {
+                signature_slot: 8,
+                current_slot: 9000,
+            },
+            47,
+            "update signed at slot 8 is too old at slot 9000",
+        ),
+        (
+            LightClientError::UntrustedBootstrapHeader,
+            50,
+            "bootstrap header does not match the trusted block root",
+        ),
+        (
+            LightClientError::InvalidLightClientHeader,
+            60,
+            "execution payload does not match the fork of the header",
+        ),
+        (
+            LightClientError::InvalidTrieProof,
+            70,
+            "trie proof does not match the root",
+        ),
+    ]
+}
+
+#[test]
+fn every_error_keeps_its_code_and_message() {
+    for (error, code, message) in cases() {
+        assert_eq!(error.code(), code, "{:?}", error);
+        assert_eq!(error.to_string(), message);
+    }
+}
+
+#[test]
+fn codes_are_unique() {
+    let mut codes: Vec<u32> = cases().iter().map(|(error, _, _)| error.code()).collect();
+    codes.sort_unstable();
+    codes.dedup();
+    assert_eq!(codes.len(), cases().len());
+}
+
+#[test]
+fn chain_errors_name_the_failing_update() {
+    let error = UpdateChainError {
+        index: Some(2),
+        error: LightClientError::IrrelevantUpdate,
+    };
+    assert_eq!(
+        error.to_string(),
+        "update 2: update is not relevant to the store"
+    );
+    let error = UpdateChainError {
+        index: None,
+        error: LightClientError::InvalidSlotOrder,
+    };
+    assert_eq!(
+        error.to_string(),
+        "checkpoint: update slots are out of order"
+    );
+}
@@ -0,0 +1,17 @@
+#!/usr/bin/env bash
+# Vendors the light client vectors of the consensus spec tests for `tests/spec_tests.rs`.
+# Usage: tests/fixtures/fetch-consensus-spec-tests.sh [release tag]
+set -euo pipefail
+
+VERSION=${1:-v1.5.0}
+TARGET="$(dirname "$0")/consensus-spec-tests"
+RELEASES=https://github.com/ethereum/consensus-spec-tests/releases/download
+
+rm -rf "$TARGET"
+mkdir -p "$TARGET"
+for preset in mainnet minimal; do
+    curl -fsSL "$RELEASES/$VERSION/$preset.tar.gz" | tar -xz -C "$TARGET" --wildcards \
+        "tests/$preset/*/light_client/sync" \
+        "tests/$preset/*/light_client/update_ranking"
+done
+echo "$VERSION" > "$TARGET/VERSION"
@@ -0,0 +1,92 @@
+//! Known answers for both hashers, and for the execution payload header roots merkleized with
+//! them. The header roots were computed with Python's `hashlib` from the SSZ definitions.
+
+use bacon::utils::is_valid_light_client_header;
+use bacon::*;
+
+mod common;
+use common::*;
+
+// The FIPS 180-2 examples, plus the empty message.
+fn known_answers() -> Vec<(Vec<u8>, &'static str)> {
+    vec![
+        (
+            b"".to_vec(),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        ),
+        (
+            b"abc".to_vec(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        ),
+        (
+            b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq".to_vec(),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+        ),
+        (
+            vec![b'a'; 1_000_000],
+            "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0",
+        ),
+    ]
+}
+
+fn assert_known_answers<H: Hasher>() {
+    for (message, digest) in known_answers() {
+        assert_eq!(
+            H::hash(&message),
+            from_hex(digest),
+            "{} bytes",
+            message.len()
+        );
+    }
+}
+
+fn assert_execution_roots<H: Hasher>() {
+    let spec = ChainSpec::mainnet().with_hasher::<H>();
+    let capella =
+        header_committing_to::<H>(slot_of(spec.capella), execution(0), CAPELLA_EXECUTION_ROOT);
+    assert!(is_valid_light_client_header(&capella, &spec));
+
+    // From Deneb on the blob gas fields are part of the container, even when zero.
+    let deneb =
+        header_committing_to::<H>(slot_of(spec.deneb), execution(131072), DENEB_EXECUTION_ROOT);
+    assert!(is_valid_light_client_header(&deneb, &spec));
+    let capella_root_in_deneb =
+        header_committing_to::<H>(slot_of(spec.deneb), execution(0), CAPELLA_EXECUTION_ROOT);
+    assert!(!is_valid_light_client_header(&capella_root_in_deneb, &spec));
+}
+
+#[test]
+fn software_hasher_matches_known_answers() {
+    assert_known_answers::<SoftwareHasher>();
+}
+
+#[test]
+fn software_hasher_merkleizes_execution_payload_headers() {
+    assert_execution_roots::<SoftwareHasher>();
+}
+
+#[cfg(feature = "sha2")]
+#[test]
+fn sha2_hasher_matches_known_answers() {
+    assert_known_answers::<Sha2Hasher>();
+}
+
+#[cfg(feature = "sha2")]
+#[test]
+fn sha2_hasher_merkleizes_execution_payload_headers() {
+    assert_execution_roots::<Sha2Hasher>();
+}
+
+#[cfg(feature = "sha2")]
+#[test]
+fn hashers_agree_around_the_block_boundaries() {
+    let message: Vec<u8> = (0..200).map(|byte| byte as u8).collect();
+    for len in 0..message.len() {
+        assert_eq!(
+            SoftwareHasher::hash(&message[..len]),
+            Sha2Hasher::hash(&message[..len]),
+            "{} bytes",
+            len
+        );
+    }
+}
@@ -0,0 +1,117 @@
+//! Proofs built over a leaf list have to verify against the root of the same tree, and stop
+//! verifying as soon as any node changes.
+
+use bacon::merkle::*;
+use bacon::*;
+
+fn leaves(count: u8) -> Vec<H256> {
+    (0..count).map(|leaf| H256([leaf + 1; 32])).collect()
+}
+
+fn root(leaves: &[H256]) -> H256 {
+    merkle_tree::<DefaultHasher>(leaves)[1]
+}
+
+fn gindex(depth: u64, index: u64) -> GeneralizedIndex {
+    (1 << depth) + index
+}
+
+#[test]
+fn single_proofs_verify_for_every_leaf() {
+    let leaves = leaves(11);
+    let root = root(&leaves);
+    for (index, leaf) in leaves.iter().enumerate() {
+        let gindex = gindex(4, index as u64);
+        let proof = build_proof::<DefaultHasher>(&leaves, gindex).unwrap();
+        assert_eq!(proof.len(), 4);
+        assert!(is_valid_merkle_branch::<DefaultHasher>(*leaf, proof.clone(), gindex, root));
+        assert!(!is_valid_merkle_branch::<DefaultHasher>(*leaf, proof.clone(), gindex ^ 1, root));
+        assert!(!is_valid_merkle_branch::<DefaultHasher>(*leaf, proof[..3].to_vec(), gindex, root));
+    }
+}
+
+#[test]
+fn inner_nodes_are_proven_like_leaves() {
+    let leaves = leaves(8);
+    let tree = merkle_tree::<DefaultHasher>(&leaves);
+    let proof = build_proof::<DefaultHasher>(&leaves, 5).unwrap();
+    assert!(is_valid_merkle_branch::<DefaultHasher>(tree[5], proof, 5, tree[1]));
+}
+
+#[test]
+fn multiproofs_share_their_branches() {
+    let leaves = leaves(16);
+    let root = root(&leaves);
+    let indices = [gindex(4, 0), gindex(4, 1), gindex(4, 6), gindex(4, 13)];
+    let proven: Vec<H256> = [0, 1, 6, 13].iter().map(|index| leaves[*index]).collect();
+    let proof = build_multiproof::<DefaultHasher>(&leaves, &indices).unwrap();
+    assert_eq!(proof.len(), get_helper_indices(&indices).len());
+    // Four separate branches would take 16 nodes.
+    assert!(proof.len() < 4 * 4);
+    assert!(verify_merkle_multiproof::<DefaultHasher>(&proven, &proof, &indices, root));
+
+    let mut tampered = proof.clone();
+    tampered[2].0[0] ^= 1;
+    assert!(!verify_merkle_multiproof::<DefaultHasher>(&proven, &tampered, &indices, root));
+    assert!(!verify_merkle_multiproof::<DefaultHasher>(&proven, &proof[1..], &indices, root));
+    assert!(!verify_merkle_multiproof::<DefaultHasher>(&proven[1..], &proof, &indices[1..], root));
+}
+
+#[test]
+fn zero_is_not_a_generalized_index() {
+    let leaves = leaves(4);
+    let tree = merkle_tree::<DefaultHasher>(&leaves);
+    assert_eq!(get_generalized_index_length(0), 0);
+    assert_eq!(compute_merkle_root::<DefaultHasher>(tree[1], vec![], 0), None);
+    assert!(!is_valid_merkle_branch::<DefaultHasher>(tree[1], vec![], 0, tree[1]));
+    assert!(!is_valid_merkle_branch::<DefaultHasher>(tree[0], vec![], 0, tree[0]));
+    assert_eq!(build_proof::<DefaultHasher>(&leaves, 0), None);
+
+    // A leaf claimed at zero is not ignored next to a valid proof of the other ones.
+    let indices = [0, 2, 3];
+    let proven = [H256([0xaa; 32]), tree[2], tree[3]];
+    assert!(!verify_merkle_multiproof::<DefaultHasher>(&proven, &[], &indices, tree[1]));
+}
+
+#[test]
+fn generalized_indices_concatenate_across_containers() {
+    assert_eq!(concat_generalized_indices(&[]), 1);
+    assert_eq!(concat_generalized_indices(&[1, 5, 1]), 5);
+    assert_eq!(concat_generalized_indices(&[2, 3]), 5);
+    assert_eq!(concat_generalized_indices(&[5, 6]), 22);
+    assert_eq!(get_generalized_index_length(22), 4);
+    assert_eq!(get_subtree_index(22), 6);
+
+    // A leaf of the subtree at gindex 5 is proven from the outer root with the concatenated index.
+    let leaves = leaves(16);
+    let tree = merkle_tree::<DefaultHasher>(&leaves);
+    let gindex = concat_generalized_indices(&[5, 6]);
+    let proof = build_proof::<DefaultHasher>(&leaves, gindex).unwrap();
+    assert!(is_valid_merkle_branch::<DefaultHasher>(tree[gindex as usize], proof, gindex, tree[1]));
+}
+
+#[test]
+fn state_gindices_match_depth_and_index() {
+    assert_eq!(
+        NEXT_SYNC_COMMITTEE_GINDEX,
+        gindex(NEXT_SYNC_COMMITTEE_DEPTH, NEXT_SYNC_COMMITTEE_INDEX)
+    );
+    assert_eq!(
+        CURRENT_SYNC_COMMITTEE_GINDEX,
+        gindex(CURRENT_SYNC_COMMITTEE_DEPTH, CURRENT_SYNC_COMMITTEE_INDEX)
+    );
+    assert_eq!(FINALIZED_ROOT_GINDEX, gindex(FINALIZED_ROOT_DEPTH, FINALIZED_ROOT_INDEX));
+    assert_eq!(
+        NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA,
+        gindex(NEXT_SYNC_COMMITTEE_DEPTH_ELECTRA, NEXT_SYNC_COMMITTEE_INDEX_ELECTRA)
+    );
+    assert_eq!(
+        CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA,
+        gindex(CURRENT_SYNC_COMMITTEE_DEPTH_ELECTRA, CURRENT_SYNC_COMMITTEE_INDEX_ELECTRA)
+    );
+    assert_eq!(
+        FINALIZED_ROOT_GINDEX_ELECTRA,
+        gindex(FINALIZED_ROOT_DEPTH_ELECTRA, FINALIZED_ROOT_INDEX_ELECTRA)
+    );
+    assert_eq!(EXECUTION_PAYLOAD_GINDEX, gindex(EXECUTION_PAYLOAD_DEPTH, EXECUTION_PAYLOAD_INDEX));
+}
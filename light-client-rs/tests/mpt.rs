@@ -0,0 +1,273 @@
+//! The tries are built by hand from their node encodings, the proofs are the nodes on the path to
+//! a key as `eth_getProof` and `eth_getTransactionReceipt` based provers return them.
+
+use bacon::mpt::*;
+use bacon::utils::is_valid_light_client_header;
+use bacon::*;
+use rlp::RlpStream;
+
+mod common;
+use common::*;
+
+fn nibbles(key: &[u8]) -> Vec<u8> {
+    key.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]).collect()
+}
+
+fn hex_prefix(nibbles: &[u8], is_leaf: bool) -> Vec<u8> {
+    let flag = if is_leaf { 2 } else { 0 };
+    let (mut path, rest) = if nibbles.len() % 2 == 1 {
+        (vec![(flag + 1) << 4 | nibbles[0]], &nibbles[1..])
+    } else {
+        (vec![flag << 4], nibbles)
+    };
+    path.extend(rest.chunks(2).map(|pair| pair[0] << 4 | pair[1]));
+    path
+}
+
+fn leaf(nibbles: &[u8], value: &[u8]) -> Vec<u8> {
+    let mut stream = RlpStream::new_list(2);
+    stream.append(&hex_prefix(nibbles, true)).append(&value);
+    stream.out().to_vec()
+}
+
+fn extension(nibbles: &[u8], child: &[u8]) -> Vec<u8> {
+    let mut stream = RlpStream::new_list(2);
+    stream.append(&hex_prefix(nibbles, false));
+    append_reference(&mut stream, child);
+    stream.out().to_vec()
+}
+
+fn branch(children: &[(u8, Vec<u8>)]) -> Vec<u8> {
+    let mut stream = RlpStream::new_list(17);
+    for nibble in 0..16 {
+        match children.iter().find(|(index, _)| *index == nibble) {
+            Some((_, child)) => append_reference(&mut stream, child),
+            None => {
+                stream.append_empty_data();
+            }
+        }
+    }
+    stream.append_empty_data();
+    stream.out().to_vec()
+}
+
+// Nodes shorter than a hash are embedded in their parent.
+fn append_reference(stream: &mut RlpStream, child: &[u8]) {
+    if child.len() < 32 {
+        stream.append_raw(child, 1);
+    } else {
+        stream.append(&keccak256(child).to_vec());
+    }
+}
+
+fn account(nonce: u64) -> Account {
+    let mut balance = [0u8; 32];
+    balance[24..].copy_from_slice(&1_000_000_000u64.to_be_bytes());
+    Account {
+        nonce,
+        balance,
+        storage_root: H256([0x33; 32]),
+        code_hash: H256([0x44; 32]),
+    }
+}
+
+fn encode_account(account: &Account) -> Vec<u8> {
+    let balance = &account.balance[account.balance.iter().take_while(|byte| **byte == 0).count()..];
+    let mut stream = RlpStream::new_list(4);
+    stream
+        .append(&account.nonce)
+        .append(&balance)
+        .append(&account.storage_root.0.to_vec())
+        .append(&account.code_hash.0.to_vec());
+    stream.out().to_vec()
+}
+
+fn address(seed: u8) -> [u8; 20] {
+    [seed; 20]
+}
+
+#[test]
+fn accounts_are_proven_by_a_single_leaf() {
+    let key = nibbles(&keccak256(&address(1)));
+    let node = leaf(&key, &encode_account(&account(7)));
+    let root = H256(keccak256(&node));
+
+    let proven = verify_account_proof(root, &address(1), &[&node]).unwrap();
+    assert_eq!(proven, Some(account(7)));
+    // The leaf holds another key, which proves the account doesn't exist.
+    assert_eq!(verify_account_proof(root, &address(2), &[&node]).unwrap(), None);
+
+    let mut tampered = node.clone();
+    *tampered.last_mut().unwrap() ^= 1;
+    assert_eq!(
+        verify_account_proof(root, &address(1), &[&tampered]).unwrap_err(),
+        LightClientError::InvalidTrieProof
+    );
+    assert_eq!(
+        verify_account_proof(root, &address(1), &[&node, &node]).unwrap_err(),
+        LightClientError::InvalidTrieProof
+    );
+    assert_eq!(
+        verify_account_proof::<Vec<u8>>(root, &address(1), &[]).unwrap_err(),
+        LightClientError::InvalidTrieProof
+    );
+}
+
+#[test]
+fn branches_lead_to_leaves_or_prove_absence() {
+    // Three addresses whose keys start with distinct nibbles.
+    let mut addresses = Vec::new();
+    for seed in 0..=u8::MAX {
+        let first = nibbles(&keccak256(&address(seed)))[0];
+        if addresses.iter().all(|(_, other)| *other != first) {
+            addresses.push((seed, first));
+        }
+        if addresses.len() == 3 {
+            break;
+        }
+    }
+    let leaves: Vec<(u8, Vec<u8>)> = addresses[..2]
+        .iter()
+        .map(|(seed, first)| {
+            let key = nibbles(&keccak256(&address(*seed)));
+            (*first, leaf(&key[1..], &encode_account(&account(*seed as u64))))
+        })
+        .collect();
+    let root_node = branch(&leaves);
+    let root = H256(keccak256(&root_node));
+
+    for ((seed, _), (_, leaf)) in addresses.iter().zip(&leaves) {
+        let proven = verify_account_proof(root, &address(*seed), &[&root_node, leaf]).unwrap();
+        assert_eq!(proven, Some(account(*seed as u64)));
+    }
+    let (absent, _) = addresses[2];
+    assert_eq!(verify_account_proof(root, &address(absent), &[&root_node]).unwrap(), None);
+}
+
+#[test]
+fn extensions_and_embedded_nodes_are_followed() {
+    let children = vec![(2, leaf(&[], b"two")), (3, leaf(&[], b"three"))];
+    let root_node = extension(&[1], &branch(&children));
+    let root = H256(keccak256(&root_node));
+
+    assert_eq!(verify_proof(root, &[0x12], &[&root_node]).unwrap(), Some(b"two".to_vec()));
+    assert_eq!(verify_proof(root, &[0x13], &[&root_node]).unwrap(), Some(b"three".to_vec()));
+    assert_eq!(verify_proof(root, &[0x14], &[&root_node]).unwrap(), None);
+    assert_eq!(verify_proof(root, &[0x22], &[&root_node]).unwrap(), None);
+}
+
+#[test]
+fn storage_slots_read_as_big_endian_words() {
+    let slot = H256([0; 32]);
+    let value = rlp::encode(&42u64).to_vec();
+    let node = leaf(&nibbles(&keccak256(&slot.0)), &value);
+    let storage_root = H256(keccak256(&node));
+
+    let mut expected = [0u8; 32];
+    expected[31] = 42;
+    assert_eq!(verify_storage_proof(storage_root, &slot, &[&node]).unwrap(), expected);
+    assert_eq!(verify_storage_proof(storage_root, &H256([1; 32]), &[&node]).unwrap(), [0; 32]);
+    assert_eq!(verify_storage_proof::<Vec<u8>>(EMPTY_TRIE_ROOT, &slot, &[]).unwrap(), [0; 32]);
+}
+
+#[test]
+fn headers_without_an_execution_payload_have_no_state_root() {
+    let header = LightClientHeader::default();
+    let spec = ChainSpec::mainnet();
+    assert_eq!(
+        verify_account_proof_at_header::<Vec<u8>, _>(&header, &address(1), &[], &spec)
+            .unwrap_err(),
+        LightClientError::InvalidLightClientHeader
+    );
+
+    // Newer containers carry an empty payload for headers from before Capella, it isn't one.
+    let update = SyncCommitteePeriodUpdate {
+        finalized_header: LightClientHeader::default(),
+        ..period_update(slot_of(spec.capella) + 2 * SLOTS_PER_EPOCH, 1)
+    };
+    let header = through_ssz(&update, &spec).finalized_header;
+    assert!(header.execution.as_ref().unwrap().is_empty());
+    assert!(is_valid_light_client_header(&header, &spec));
+    assert_eq!(
+        header.verified_execution(&spec).unwrap_err(),
+        LightClientError::InvalidLightClientHeader
+    );
+}
+
+fn log(seed: u8) -> Log {
+    Log {
+        address: [seed; 20],
+        topics: vec![H256([seed; 32]), H256([seed + 1; 32])],
+        data: vec![seed; 40],
+    }
+}
+
+fn encode_receipt(transaction_type: u8, logs: &[Log]) -> Vec<u8> {
+    let mut stream = RlpStream::new_list(4);
+    stream
+        .append(&1u8)
+        .append(&21_000u64)
+        .append(&vec![0u8; BYTES_PER_LOGS_BLOOM]);
+    stream.begin_list(logs.len());
+    for log in logs {
+        stream.begin_list(3).append(&log.address.to_vec());
+        stream.begin_list(log.topics.len());
+        for topic in &log.topics {
+            stream.append(&topic.0.to_vec());
+        }
+        stream.append(&log.data);
+    }
+    let mut encoded = stream.out().to_vec();
+    if transaction_type != 0 {
+        encoded.insert(0, transaction_type);
+    }
+    encoded
+}
+
+#[test]
+fn receipts_are_proven_by_transaction_index() {
+    // rlp(0) is 0x80 and rlp(1) is 0x01, the keys part at their first nibble.
+    let legacy = encode_receipt(0, &[log(1)]);
+    let dynamic_fee = encode_receipt(2, &[log(2), log(3)]);
+    let leaves = vec![(8, leaf(&[0], &legacy)), (0, leaf(&[1], &dynamic_fee))];
+    let root_node = branch(&leaves);
+    let root = H256(keccak256(&root_node));
+
+    let receipt = verify_receipt_proof(root, 0, &[&root_node, &leaves[0].1]).unwrap().unwrap();
+    assert_eq!(receipt.transaction_type, TransactionType::Legacy);
+    assert!(receipt.success);
+    assert_eq!(receipt.cumulative_gas_used, 21_000);
+    assert_eq!(receipt.logs, vec![log(1)]);
+
+    let receipt = verify_receipt_proof(root, 1, &[&root_node, &leaves[1].1]).unwrap().unwrap();
+    assert_eq!(receipt.transaction_type, TransactionType::DynamicFee);
+    assert_eq!(receipt.logs, vec![log(2), log(3)]);
+    let matching: Vec<&Log> = receipt.logs_with_topic(&[3; 20], &H256([3; 32])).collect();
+    assert_eq!(matching, vec![&log(3)]);
+
+    // rlp(0x30) is 0x30, nothing is stored under its first nibble.
+    assert_eq!(verify_receipt_proof(root, 0x30, &[&root_node]).unwrap(), None);
+    assert_eq!(verify_receipt_proof(root, 2, &[&root_node, &leaves[1].1]).unwrap(), None);
+    assert_eq!(
+        verify_receipt_proof(root, 1, &[&root_node, &leaves[0].1]).unwrap_err(),
+        LightClientError::InvalidTrieProof
+    );
+}
+
+#[test]
+fn every_typed_receipt_decodes() {
+    for (byte, transaction_type) in [
+        (1, TransactionType::AccessList),
+        (2, TransactionType::DynamicFee),
+        (3, TransactionType::Blob),
+        (4, TransactionType::SetCode),
+    ] {
+        let receipt = Receipt::decode(&encode_receipt(byte, &[log(byte)])).unwrap();
+        assert_eq!(receipt.transaction_type, transaction_type);
+        assert_eq!(receipt.logs, vec![log(byte)]);
+    }
+    assert_eq!(
+        Receipt::decode(&encode_receipt(0x7e, &[])).unwrap_err(),
+        LightClientError::Decode { field: "receipt" }
+    );
+}
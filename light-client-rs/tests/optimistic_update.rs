@@ -0,0 +1,104 @@
+//! Optimistic updates track the head with the signature of the current committee alone, without
+//! a finalized header or a branch.
+
+use bacon::*;
+
+mod common;
+use common::*;
+
+const ATTESTED_SLOT: u64 = PERIOD + 5;
+
+fn encoded_committee(sync_committee: &SyncCommittee) -> Vec<u8> {
+    let mut bytes = sync_committee
+        .pubkeys
+        .iter()
+        .flat_map(|pubkey| pubkey.0)
+        .collect::<Vec<_>>();
+    bytes.extend(sync_committee.aggregate_pubkey.0);
+    bytes
+}
+
+fn signed_update(spec: &ChainSpec, participants: usize) -> LightClientOptimisticUpdate {
+    let attested_header = valid_header(spec, ATTESTED_SLOT);
+    let signature = sign(
+        &attested_header.beacon,
+        participants,
+        ATTESTED_SLOT + 1,
+        spec,
+    );
+    LightClientOptimisticUpdate {
+        attested_header,
+        sync_aggregate: SyncAggregate {
+            sync_committee_bits: bits(|index| index < participants),
+            sync_committee_signature: signature,
+        },
+        signature_slot: ATTESTED_SLOT + 1,
+    }
+}
+
+fn process(
+    update: &LightClientOptimisticUpdate,
+    spec: &ChainSpec,
+) -> Result<(LightClientHeader, H256), LightClientError> {
+    ssz_process_optimistic_update(
+        &update.to_ssz_bytes(spec).unwrap(),
+        &encoded_committee(&sync_committee()),
+        update.signature_slot,
+        &VerificationPolicy::spec(),
+        spec,
+    )
+}
+
+#[test]
+fn signed_updates_return_the_attested_header() {
+    for spec in [altair_spec(), ChainSpec::devnet(H256([7; 32]), 0)] {
+        let update = signed_update(&spec, MIN_SYNC_COMMITTEE_PARTICIPANTS as usize);
+        let root = update.attested_header.beacon.hash_tree_root();
+        assert_eq!(process(&update, &spec), Ok((update.attested_header, root)));
+    }
+}
+
+#[test]
+fn unsigned_or_out_of_order_updates_are_rejected() {
+    let spec = altair_spec();
+    let update = signed_update(&spec, SUPERMAJORITY);
+
+    let same_slot = LightClientOptimisticUpdate {
+        signature_slot: ATTESTED_SLOT,
+        ..update.clone()
+    };
+    assert_eq!(
+        process(&same_slot, &spec),
+        Err(LightClientError::InvalidSlotOrder)
+    );
+
+    let mut other_header = update.clone();
+    other_header.attested_header.beacon.proposer_index += 1;
+    assert_eq!(
+        process(&other_header, &spec),
+        Err(LightClientError::Bls(BlsError::VerificationFailed))
+    );
+
+    let participants = MIN_SYNC_COMMITTEE_PARTICIPANTS - 1;
+    assert_eq!(
+        process(&signed_update(&spec, participants as usize), &spec),
+        Err(LightClientError::InsufficientParticipation {
+            participants,
+            required: MIN_SYNC_COMMITTEE_PARTICIPANTS,
+        })
+    );
+
+    let committee = encoded_committee(&sync_committee());
+    assert_eq!(
+        ssz_process_optimistic_update(
+            &update.to_ssz_bytes(&spec).unwrap(),
+            &committee[PUBKEY_SIZE..],
+            update.signature_slot,
+            &VerificationPolicy::spec(),
+            &spec,
+        ),
+        Err(LightClientError::Decode {
+            field: "sync_committee"
+        })
+    );
+}
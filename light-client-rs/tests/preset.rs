@@ -0,0 +1,91 @@
+//! The preset fixes the size of the sync committee containers, a build decodes the updates of its
+//! own preset only.
+
+use bacon::*;
+
+mod common;
+use common::*;
+
+const OTHER_SYNC_COMMITTEE_SIZE: usize = if IS_MINIMAL { 512 } else { 32 };
+
+// The Altair container, all of its fields have a fixed size.
+const HEADER_SIZE: usize = 112;
+
+fn committee_size(sync_committee_size: usize) -> usize {
+    (sync_committee_size + 1) * PUBKEY_SIZE
+}
+
+fn aggregate_size(sync_committee_size: usize) -> usize {
+    sync_committee_size / 8 + 96
+}
+
+fn encoded_update() -> Vec<u8> {
+    period_update(PERIOD + 5, SUPERMAJORITY)
+        .to_ssz_bytes(&altair_spec())
+        .unwrap()
+}
+
+// `bytes` with the committee and the sync aggregate resized to `sync_committee_size` members.
+fn resized(bytes: &[u8], sync_committee_size: usize) -> Vec<u8> {
+    let committee = HEADER_SIZE..HEADER_SIZE + committee_size(SYNC_COMMITTEE_SIZE);
+    let aggregate_start = committee.end
+        + 32 * (NEXT_SYNC_COMMITTEE_DEPTH as usize)
+        + HEADER_SIZE
+        + 32 * (FINALIZED_ROOT_DEPTH as usize);
+    let aggregate = aggregate_start..aggregate_start + aggregate_size(SYNC_COMMITTEE_SIZE);
+
+    let mut resized = bytes[..committee.start].to_vec();
+    resized.extend(G1_GENERATOR.repeat(sync_committee_size + 1));
+    resized.extend(&bytes[committee.end..aggregate.start]);
+    resized.extend(vec![0xff; sync_committee_size / 8]);
+    resized.extend(&bytes[aggregate.end - 96..]);
+    resized
+}
+
+#[test]
+fn the_preset_sizes_the_containers() {
+    if IS_MINIMAL {
+        assert_eq!(
+            (
+                SYNC_COMMITTEE_SIZE,
+                SLOTS_PER_EPOCH,
+                EPOCHS_PER_SYNC_COMMITTEE_PERIOD
+            ),
+            (32, 8, 8)
+        );
+    } else {
+        assert_eq!(
+            (
+                SYNC_COMMITTEE_SIZE,
+                SLOTS_PER_EPOCH,
+                EPOCHS_PER_SYNC_COMMITTEE_PERIOD
+            ),
+            (512, 32, 256)
+        );
+    }
+
+    let bytes = encoded_update();
+    assert_eq!(
+        bytes.len(),
+        2 * HEADER_SIZE
+            + committee_size(SYNC_COMMITTEE_SIZE)
+            + 32 * (NEXT_SYNC_COMMITTEE_DEPTH + FINALIZED_ROOT_DEPTH) as usize
+            + aggregate_size(SYNC_COMMITTEE_SIZE)
+            + 8
+    );
+    // Resizing to the own preset changes nothing but the participation.
+    let update =
+        SyncCommitteePeriodUpdate::decode(&resized(&bytes, SYNC_COMMITTEE_SIZE), &altair_spec())
+            .unwrap();
+    assert_eq!(update.next_sync_committee.pubkeys, sync_committee().pubkeys);
+    assert_eq!(update.sync_aggregate.sync_committee_bits, bits(|_| true));
+    assert_eq!(update.signature_slot, PERIOD + 6);
+}
+
+#[test]
+fn updates_of_the_other_preset_are_rejected() {
+    let bytes = resized(&encoded_update(), OTHER_SYNC_COMMITTEE_SIZE);
+    let spec = altair_spec();
+    assert!(SyncCommitteePeriodUpdate::decode(&bytes, &spec).is_err());
+    assert!(SyncCommitteePeriodUpdateView::new(&bytes, &spec).is_err());
+}
@@ -0,0 +1,89 @@
+//! The signature slot of the canonical update decides which committee signed it and in which
+//! domain, the update has no other say in either.
+
+use bacon::*;
+
+mod common;
+use common::*;
+
+// Altair for the first period and an epoch of the second, Bellatrix after it.
+fn spec() -> ChainSpec {
+    let mut spec = altair_spec();
+    spec.bellatrix.epoch = EPOCHS_PER_SYNC_COMMITTEE_PERIOD + 1;
+    spec
+}
+
+// Attested in the first period, only its slots and next sync committee are used.
+fn prev_update() -> Vec<u8> {
+    period_update(3 * SLOTS_PER_EPOCH, 0)
+        .to_ssz_bytes(&spec())
+        .unwrap()
+}
+
+fn process(update: &SyncCommitteePeriodUpdate) -> Result<LightClientHeader, LightClientError> {
+    let spec = spec();
+    let update = update.to_ssz_bytes(&spec).unwrap();
+    let policy = VerificationPolicy::spec();
+    ssz_process_sync_committee_period_update(&prev_update(), &update, &policy, &spec)
+        .map(|(_, finalized_header, _)| finalized_header)
+}
+
+#[test]
+fn signature_slot_is_the_last_field_of_the_update() {
+    let spec = spec();
+    let update = signed_period_update(&spec, PERIOD + 5, SUPERMAJORITY);
+    let bytes = update.to_ssz_bytes(&spec).unwrap();
+    assert_eq!(bytes[bytes.len() - 8..], (PERIOD + 6).to_le_bytes());
+    assert_eq!(
+        SyncCommitteePeriodUpdate::decode(&bytes, &spec)
+            .unwrap()
+            .signature_slot,
+        PERIOD + 6
+    );
+    assert_eq!(process(&update), Ok(update.finalized_header));
+}
+
+#[test]
+fn the_committee_of_the_next_period_signs_until_its_last_slot() {
+    let spec = spec();
+    let update = signed_period_update(&spec, 2 * PERIOD - 2, SUPERMAJORITY);
+    assert_eq!(update.signature_slot, 2 * PERIOD - 1);
+    assert_eq!(process(&update), Ok(update.finalized_header));
+
+    // Signed in the period after, by a committee the previous update doesn't know.
+    let update = signed_period_update(&spec, 2 * PERIOD - 1, SUPERMAJORITY);
+    assert_eq!(
+        process(&update),
+        Err(LightClientError::InvalidSyncCommitteePeriod {
+            store_period: 0,
+            update_period: 2,
+        })
+    );
+}
+
+#[test]
+fn the_signature_slot_selects_the_domain() {
+    let spec = spec();
+    let update = signed_period_update(&spec, PERIOD + 5, SUPERMAJORITY);
+    let first_bellatrix_slot = spec.bellatrix.epoch * SLOTS_PER_EPOCH;
+
+    // Still signing the last Altair block, the signature stays valid.
+    let update = SyncCommitteePeriodUpdate {
+        signature_slot: first_bellatrix_slot,
+        ..update
+    };
+    assert_eq!(process(&update), Ok(update.finalized_header.clone()));
+
+    let update = SyncCommitteePeriodUpdate {
+        signature_slot: first_bellatrix_slot + 1,
+        ..update
+    };
+    assert_eq!(
+        process(&update),
+        Err(LightClientError::Bls(BlsError::VerificationFailed))
+    );
+    assert_eq!(
+        process(&signed(update.clone(), SUPERMAJORITY, &spec)),
+        Ok(update.finalized_header)
+    );
+}
@@ -0,0 +1,396 @@
+//! Runs the `light_client` vectors of the consensus spec tests.
+//!
+//! The vectors are fetched into `tests/fixtures/consensus-spec-tests` by
+//! `tests/fixtures/fetch-consensus-spec-tests.sh`, as the snappy framed SSZ and YAML files of the
+//! release. They aren't committed, so the tests only run with `CONSENSUS_SPEC_TESTS` set, as CI
+//! does after fetching them. Only the preset bacon is built with can run, cases of the other
+//! preset, of forks bacon doesn't know and of features it doesn't support are reported as skipped
+//! with the reason. Missing fixtures, or no cases for the preset bacon is built with, fail the
+//! tests. Run with `--nocapture` to see the report.
+//!
+//! The `single_merkle_proof` vectors aren't run, their root is the one of a whole `BeaconState` or
+//! `BeaconBlockBody`, which bacon doesn't model. The sync cases verify the same branches against
+//! the state roots of their headers.
+
+use bacon::chain_spec::FAR_FUTURE_EPOCH;
+use bacon::merkle::merkle_tree;
+use bacon::*;
+use serde_yaml::Value;
+use std::fs;
+use std::io::Read;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+const PRESETS: [&str; 2] = ["mainnet", "minimal"];
+
+fn compiled_preset() -> &'static str {
+    if IS_MINIMAL {
+        "minimal"
+    } else {
+        "mainnet"
+    }
+}
+
+fn fixtures() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/consensus-spec-tests/tests")
+}
+
+enum Outcome {
+    Skip(String),
+    Fail(String),
+}
+
+type CaseResult = Result<(), Outcome>;
+
+fn fail(message: impl ToString) -> Outcome {
+    Outcome::Fail(message.to_string())
+}
+
+fn skip(reason: impl ToString) -> Outcome {
+    Outcome::Skip(reason.to_string())
+}
+
+#[derive(Default)]
+struct Report {
+    passed: usize,
+    skipped: Vec<(String, String)>,
+    failed: Vec<(String, String)>,
+}
+
+impl Report {
+    fn record(&mut self, case: &Path, result: CaseResult) {
+        let name = case
+            .strip_prefix(fixtures())
+            .unwrap_or(case)
+            .display()
+            .to_string();
+        match result {
+            Ok(()) => self.passed += 1,
+            Err(Outcome::Skip(reason)) => self.skipped.push((name, reason)),
+            Err(Outcome::Fail(message)) => self.failed.push((name, message)),
+        }
+    }
+
+    fn finish(self, handler: &str) {
+        println!(
+            "light_client/{}: {} passed, {} skipped, {} failed",
+            handler,
+            self.passed,
+            self.skipped.len(),
+            self.failed.len()
+        );
+        for (case, reason) in &self.skipped {
+            println!("  skipped {}: {}", case, reason);
+        }
+        for (case, message) in &self.failed {
+            println!("  FAILED {}: {}", case, message);
+        }
+        assert!(
+            self.failed.is_empty(),
+            "{} light_client/{} cases failed",
+            self.failed.len(),
+            handler
+        );
+    }
+}
+
+fn fork_name(directory: &str) -> Option<ForkName> {
+    match directory {
+        "altair" => Some(ForkName::Altair),
+        "bellatrix" => Some(ForkName::Bellatrix),
+        "capella" => Some(ForkName::Capella),
+        "deneb" => Some(ForkName::Deneb),
+        "electra" => Some(ForkName::Electra),
+        _ => None,
+    }
+}
+
+fn subdirectories(directory: &Path) -> Vec<PathBuf> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(directory)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.is_dir())
+                .collect()
+        })
+        .unwrap_or_default();
+    entries.sort();
+    entries
+}
+
+/// Calls `run` for every case of `handler`, the case directories sit `depth` levels below the
+/// handler directory.
+fn run_handler(handler: &str, depth: usize, run: impl Fn(ForkName, &Path) -> CaseResult) {
+    if std::env::var_os("CONSENSUS_SPEC_TESTS").is_none() {
+        println!("light_client/{}: set CONSENSUS_SPEC_TESTS to run the vectors", handler);
+        return;
+    }
+    let mut report = Report::default();
+    assert!(
+        fixtures().is_dir(),
+        "light_client/{}: the fixtures aren't vendored, run \
+         tests/fixtures/fetch-consensus-spec-tests.sh",
+        handler
+    );
+    let mut compiled_cases = 0;
+    for preset in PRESETS {
+        for fork_directory in subdirectories(&fixtures().join(preset)) {
+            let mut cases = vec![fork_directory.join("light_client").join(handler)];
+            for _ in 0..depth {
+                cases = cases.iter().flat_map(|path| subdirectories(path)).collect();
+            }
+            let fork = fork_directory
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(fork_name);
+            for case in cases {
+                let result = match fork {
+                    _ if preset != compiled_preset() => {
+                        Err(skip(format!("bacon is built for the {} preset", compiled_preset())))
+                    }
+                    None => Err(skip("fork not supported by bacon")),
+                    Some(fork) => {
+                        compiled_cases += 1;
+                        run(fork, &case)
+                    }
+                };
+                report.record(&case, result);
+            }
+        }
+    }
+    assert!(
+        compiled_cases > 0,
+        "light_client/{}: no cases for the {} preset, the fixtures are incomplete",
+        handler,
+        compiled_preset()
+    );
+    report.finish(handler);
+}
+
+fn read_ssz(path: &Path) -> Result<Vec<u8>, Outcome> {
+    let file = fs::File::open(path).map_err(|e| fail(format!("{}: {}", path.display(), e)))?;
+    let mut bytes = Vec::new();
+    snap::read::FrameDecoder::new(file)
+        .read_to_end(&mut bytes)
+        .map_err(|e| fail(format!("{}: {}", path.display(), e)))?;
+    Ok(bytes)
+}
+
+fn read_yaml(path: &Path) -> Result<Value, Outcome> {
+    let file = fs::File::open(path).map_err(|e| fail(format!("{}: {}", path.display(), e)))?;
+    serde_yaml::from_reader(file).map_err(|e| fail(format!("{}: {}", path.display(), e)))
+}
+
+fn hex(value: &Value) -> Result<Vec<u8>, Outcome> {
+    let text = value.as_str().ok_or_else(|| fail(format!("{:?} is not a string", value)))?;
+    let digits = text.strip_prefix("0x").unwrap_or(text);
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(digits.get(i..i + 2).unwrap_or("zz"), 16))
+        .collect::<Result<_, _>>()
+        .map_err(|_| fail(format!("invalid hex {}", text)))
+}
+
+fn hash(value: &Value) -> Result<H256, Outcome> {
+    let bytes = hex(value)?;
+    bytes
+        .try_into()
+        .map(H256)
+        .map_err(|_| fail(format!("{:?} is not a 32 byte root", value)))
+}
+
+fn number(value: &Value) -> Result<u64, Outcome> {
+    match value {
+        Value::Number(number) => number.as_u64(),
+        Value::String(text) => text.parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| fail(format!("{:?} is not an unsigned integer", value)))
+}
+
+/// The test configuration of the compiled preset with every fork up to `fork` active from genesis.
+fn spec_at_fork(fork: ForkName, genesis_validators_root: H256) -> ChainSpec {
+    let preset_byte = if IS_MINIMAL { 1 } else { 0 };
+    let fork_at = |index: u8, name: ForkName| Fork {
+        version: [index, 0, 0, preset_byte],
+        epoch: if name <= fork { 0 } else { FAR_FUTURE_EPOCH },
+    };
+    ChainSpec {
+        genesis_validators_root,
+        genesis_time: 0,
+        genesis_fork_version: [0, 0, 0, preset_byte],
+        altair: fork_at(1, ForkName::Altair),
+        bellatrix: fork_at(2, ForkName::Bellatrix),
+        capella: fork_at(3, ForkName::Capella),
+        deneb: fork_at(4, ForkName::Deneb),
+        electra: fork_at(5, ForkName::Electra),
+        hasher: PhantomData,
+    }
+}
+
+/// Applies the fork schedule of a case's `config.yaml`, if it has one.
+fn apply_config(spec: &mut ChainSpec, case: &Path) -> CaseResult {
+    let path = case.join("config.yaml");
+    if !path.exists() {
+        return Ok(());
+    }
+    let config = read_yaml(&path)?;
+    let version = |name: &str| -> Result<ForkVersion, Outcome> {
+        match &config[name] {
+            // Unquoted hex is read as a number.
+            Value::Number(number) => {
+                Ok((number.as_u64().unwrap_or_default() as u32).to_be_bytes())
+            }
+            value => hex(value)?
+                .try_into()
+                .map_err(|_| fail(format!("invalid {}", name))),
+        }
+    };
+    let epoch = |name: &str| match &config[name] {
+        Value::Null => Ok(FAR_FUTURE_EPOCH),
+        value => number(value),
+    };
+    spec.genesis_fork_version = version("GENESIS_FORK_VERSION")?;
+    for (fork, prefix) in [
+        (&mut spec.altair, "ALTAIR"),
+        (&mut spec.bellatrix, "BELLATRIX"),
+        (&mut spec.capella, "CAPELLA"),
+        (&mut spec.deneb, "DENEB"),
+        (&mut spec.electra, "ELECTRA"),
+    ] {
+        fork.version = version(&format!("{}_FORK_VERSION", prefix))?;
+        fork.epoch = epoch(&format!("{}_FORK_EPOCH", prefix))?;
+    }
+    Ok(())
+}
+
+fn fork_digest(version: ForkVersion, genesis_validators_root: H256) -> [u8; 4] {
+    let mut chunk = [0u8; 32];
+    chunk[..4].copy_from_slice(&version);
+    let root = merkle_tree::<DefaultHasher>(&[H256(chunk), genesis_validators_root])[1];
+    root.0[..4].try_into().unwrap()
+}
+
+fn fork_of_digest(spec: &ChainSpec, digest: &Value) -> Result<ForkName, Outcome> {
+    let digest = hex(digest)?;
+    [
+        ForkName::Altair,
+        ForkName::Bellatrix,
+        ForkName::Capella,
+        ForkName::Deneb,
+        ForkName::Electra,
+    ]
+    .into_iter()
+    .find(|fork| fork_digest(spec.fork_version(*fork), spec.genesis_validators_root) == digest[..])
+    .ok_or_else(|| skip("fork digest of a fork bacon doesn't know"))
+}
+
+/// The fork whose container the `digest` of a fixture names, `None` for fixtures without one.
+/// Headers can be stored in the container of a later fork than their own.
+fn container_fork(spec: &ChainSpec, digest: &Value) -> Result<Option<ForkName>, Outcome> {
+    if digest.is_null() {
+        return Ok(None);
+    }
+    fork_of_digest(spec, digest).map(Some)
+}
+
+fn check_header(header: &LightClientHeader, expected: &Value, name: &str) -> CaseResult {
+    if expected.is_null() {
+        return Ok(());
+    }
+    let slot = number(&expected["slot"])?;
+    let beacon_root = hash(&expected["beacon_root"])?;
+    if header.beacon.slot != slot || header.beacon.hash_tree_root() != beacon_root {
+        return Err(fail(format!(
+            "{} is slot {} with root {:?}, expected slot {} with root {:?}",
+            name,
+            header.beacon.slot,
+            header.beacon.hash_tree_root(),
+            slot,
+            beacon_root
+        )));
+    }
+    Ok(())
+}
+
+fn check_store(store: &LightClientStore, checks: &Value) -> CaseResult {
+    check_header(&store.finalized_header, &checks["finalized_header"], "finalized header")?;
+    check_header(&store.optimistic_header, &checks["optimistic_header"], "optimistic header")
+}
+
+fn run_sync(fork: ForkName, case: &Path) -> CaseResult {
+    let meta = read_yaml(&case.join("meta.yaml"))?;
+    let genesis_validators_root = hash(&meta["genesis_validators_root"])?;
+    let mut spec = spec_at_fork(fork, genesis_validators_root);
+    apply_config(&mut spec, case)?;
+    let policy = VerificationPolicy::spec();
+
+    let bootstrap = read_ssz(&case.join("bootstrap.ssz_snappy"))?;
+    let bootstrap = match container_fork(&spec, &meta["bootstrap_fork_digest"])? {
+        Some(fork) => LightClientBootstrap::decode_in_fork(&bootstrap, fork, &spec),
+        None => LightClientBootstrap::decode(&bootstrap, &spec),
+    }
+    .map_err(fail)?;
+    let mut store =
+        initialize_from_bootstrap(hash(&meta["trusted_block_root"])?, bootstrap, &spec)
+            .map_err(|e| fail(format!("bootstrap: {}", e)))?;
+
+    let steps = read_yaml(&case.join("steps.yaml"))?;
+    let steps = steps.as_sequence().ok_or_else(|| fail("steps.yaml is not a list"))?;
+    for (index, step) in steps.iter().enumerate() {
+        let step_error = |e: LightClientError| fail(format!("step {}: {}", index, e));
+        if let Some(step) = step.get("process_update") {
+            let name = step["update"].as_str().ok_or_else(|| fail("update without a file"))?;
+            let update = read_ssz(&case.join(format!("{}.ssz_snappy", name)))?;
+            let update = match container_fork(&spec, &step["update_fork_digest"])? {
+                Some(fork) => SyncCommitteePeriodUpdate::decode_in_fork(&update, fork, &spec),
+                None => SyncCommitteePeriodUpdate::decode(&update, &spec),
+            }
+            .map_err(step_error)?;
+            let current_slot = number(&step["current_slot"])?;
+            process_light_client_update(&mut store, update, current_slot, &policy, &spec)
+                .map_err(step_error)?;
+            check_store(&store, &step["checks"])?;
+        } else if let Some(step) = step.get("force_update") {
+            let current_slot = number(&step["current_slot"])?;
+            process_light_client_store_force_update(&mut store, current_slot)
+                .map_err(step_error)?;
+            check_store(&store, &step["checks"])?;
+        } else if let Some(step) = step.get("upgrade_store") {
+            // The native store holds headers of every fork, there is nothing to upgrade.
+            check_store(&store, &step["checks"])?;
+        } else {
+            return Err(skip(format!("unknown step {:?}", step)));
+        }
+    }
+    Ok(())
+}
+
+fn run_update_ranking(fork: ForkName, case: &Path) -> CaseResult {
+    let meta = read_yaml(&case.join("meta.yaml"))?;
+    let spec = spec_at_fork(fork, H256::default());
+    let updates = (0..number(&meta["updates_count"])?)
+        .map(|index| {
+            let bytes = read_ssz(&case.join(format!("updates_{}.ssz_snappy", index)))?;
+            SyncCommitteePeriodUpdate::decode(&bytes, &spec)
+                .map_err(|e| fail(format!("update {}: {}", index, e)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    // The updates are listed from best to worst.
+    for (index, pair) in updates.windows(2).enumerate() {
+        if is_better_update(&pair[1], &pair[0]) {
+            return Err(fail(format!("update {} ranks above update {}", index + 1, index)));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn light_client_sync() {
+    run_handler("sync", 2, run_sync);
+}
+
+#[test]
+fn light_client_update_ranking() {
+    run_handler("update_ranking", 2, run_update_ranking);
+}
@@ -0,0 +1,268 @@
+//! Encoding a native update has to reproduce the bytes it was decoded from, the relayer derives
+//! preimage keys from them.
+
+use bacon::utils::is_valid_light_client_header;
+use bacon::*;
+
+mod common;
+use common::*;
+
+fn spec() -> ChainSpec {
+    ChainSpec::mainnet()
+}
+
+fn header_with(slot: u64, execution: Option<ExecutionPayloadHeader>) -> LightClientHeader {
+    let execution_branch = match execution {
+        Some(_) => (0..4).map(|node| H256([node; 32])).collect(),
+        None => Vec::new(),
+    };
+    LightClientHeader {
+        execution,
+        execution_branch,
+        ..header(slot)
+    }
+}
+
+fn execution() -> ExecutionPayloadHeader {
+    ExecutionPayloadHeader {
+        parent_hash: H256([4; 32]),
+        fee_recipient: [5; 20],
+        logs_bloom: vec![6; BYTES_PER_LOGS_BLOOM],
+        block_number: 7,
+        gas_limit: 30_000_000,
+        extra_data: vec![8, 9],
+        base_fee_per_gas: [10; 32],
+        blob_gas_used: 131072,
+        ..Default::default()
+    }
+}
+
+fn execution_capella() -> ExecutionPayloadHeader {
+    ExecutionPayloadHeader {
+        blob_gas_used: 0,
+        ..execution()
+    }
+}
+
+fn sync_aggregate() -> SyncAggregate {
+    SyncAggregate {
+        sync_committee_bits: bits(|index| index % 2 == 1),
+        sync_committee_signature: vec![0xbb; SIGNATURE_SIZE],
+    }
+}
+
+fn period_update_with(
+    attested_slot: u64,
+    execution: Option<ExecutionPayloadHeader>,
+) -> SyncCommitteePeriodUpdate {
+    let electra = attested_slot >= slot_of(spec().electra);
+    SyncCommitteePeriodUpdate {
+        attested_header: header_with(attested_slot, execution.clone()),
+        next_sync_committee: sync_committee(),
+        next_sync_committee_branch: vec![H256([11; 32]); if electra { 6 } else { 5 }],
+        finalized_header: header_with(attested_slot - 2 * SLOTS_PER_EPOCH, execution),
+        finality_branch: vec![H256([12; 32]); if electra { 7 } else { 6 }],
+        sync_aggregate: sync_aggregate(),
+        signature_slot: attested_slot + 1,
+    }
+}
+
+#[test]
+fn sync_committee_round_trips() {
+    let bytes = sync_committee().to_ssz_bytes().unwrap();
+    let decoded: SyncCommittee = SSZSyncCommittee::deserialize(&bytes).unwrap().into();
+    assert_eq!(decoded, sync_committee());
+}
+
+#[test]
+fn period_updates_round_trip_in_every_fork() {
+    let spec = spec();
+    let updates = [
+        period_update_with(slot_of(spec.altair) + 2 * SLOTS_PER_EPOCH, None),
+        period_update_with(
+            slot_of(spec.capella) + 2 * SLOTS_PER_EPOCH,
+            Some(execution_capella()),
+        ),
+        period_update_with(slot_of(spec.deneb) + 2 * SLOTS_PER_EPOCH, Some(execution())),
+        period_update_with(slot_of(spec.electra) + 2 * SLOTS_PER_EPOCH, Some(execution())),
+    ];
+    for update in updates {
+        let bytes = update.to_ssz_bytes(&spec).unwrap();
+        let decoded = SyncCommitteePeriodUpdate::decode(&bytes, &spec).unwrap();
+        assert_eq!(decoded.attested_header, update.attested_header);
+        assert_eq!(decoded.next_sync_committee, update.next_sync_committee);
+        assert_eq!(decoded.sync_aggregate, update.sync_aggregate);
+        assert_eq!(decoded.to_ssz_bytes(&spec).unwrap(), bytes);
+    }
+}
+
+// Attested slots in every fork from Altair on, with headers of the matching shape.
+fn slots_in_every_fork() -> Vec<u64> {
+    let spec = spec();
+    [spec.altair, spec.capella, spec.deneb, spec.electra]
+        .iter()
+        .map(|fork| slot_of(*fork) + 2 * SLOTS_PER_EPOCH)
+        .collect()
+}
+
+fn header_at(slot: u64) -> LightClientHeader {
+    match spec().fork_at_slot(slot) {
+        ForkName::Phase0 | ForkName::Altair | ForkName::Bellatrix => header_with(slot, None),
+        ForkName::Capella => header_with(slot, Some(execution_capella())),
+        ForkName::Deneb | ForkName::Electra => header_with(slot, Some(execution())),
+    }
+}
+
+fn is_electra(slot: u64) -> bool {
+    spec().fork_at_slot(slot) >= ForkName::Electra
+}
+
+#[test]
+fn finality_updates_round_trip_in_every_fork() {
+    let spec = spec();
+    for attested_slot in slots_in_every_fork() {
+        let depth = if is_electra(attested_slot) { 7 } else { 6 };
+        let update = FinalizedHeaderUpdate {
+            attested_header: header_at(attested_slot),
+            finalized_header: header_at(attested_slot - 2 * SLOTS_PER_EPOCH),
+            finality_branch: vec![H256([12; 32]); depth],
+            sync_aggregate: sync_aggregate(),
+            signature_slot: attested_slot + 1,
+        };
+        let bytes = update.to_ssz_bytes(&spec).unwrap();
+        let decoded = FinalizedHeaderUpdate::decode(&bytes, &spec).unwrap();
+        assert_eq!(decoded.attested_header, update.attested_header);
+        assert_eq!(decoded.finalized_header, update.finalized_header);
+        assert_eq!(decoded.finality_branch, update.finality_branch);
+        assert_eq!(decoded.sync_aggregate, update.sync_aggregate);
+        assert_eq!(decoded.signature_slot, update.signature_slot);
+        assert_eq!(decoded.to_ssz_bytes(&spec).unwrap(), bytes);
+    }
+}
+
+#[test]
+fn optimistic_updates_round_trip_in_every_fork() {
+    let spec = spec();
+    for attested_slot in slots_in_every_fork() {
+        let update = LightClientOptimisticUpdate {
+            attested_header: header_at(attested_slot),
+            sync_aggregate: sync_aggregate(),
+            signature_slot: attested_slot + 1,
+        };
+        let bytes = update.to_ssz_bytes(&spec).unwrap();
+        let decoded = LightClientOptimisticUpdate::decode(&bytes, &spec).unwrap();
+        assert_eq!(decoded.attested_header, update.attested_header);
+        assert_eq!(decoded.sync_aggregate, update.sync_aggregate);
+        assert_eq!(decoded.signature_slot, update.signature_slot);
+        assert_eq!(decoded.to_ssz_bytes(&spec).unwrap(), bytes);
+    }
+}
+
+#[test]
+fn bootstraps_round_trip_in_every_fork() {
+    let spec = spec();
+    for slot in slots_in_every_fork() {
+        let depth = if is_electra(slot) { 6 } else { 5 };
+        let bootstrap = LightClientBootstrap {
+            header: header_at(slot),
+            current_sync_committee: sync_committee(),
+            current_sync_committee_branch: vec![H256([13; 32]); depth],
+        };
+        let bytes = bootstrap.to_ssz_bytes(&spec).unwrap();
+        let decoded = LightClientBootstrap::decode(&bytes, &spec).unwrap();
+        assert_eq!(decoded.header, bootstrap.header);
+        assert_eq!(decoded.current_sync_committee, bootstrap.current_sync_committee);
+        assert_eq!(
+            decoded.current_sync_committee_branch,
+            bootstrap.current_sync_committee_branch
+        );
+        assert_eq!(decoded.to_ssz_bytes(&spec).unwrap(), bytes);
+    }
+}
+
+#[test]
+fn blob_gas_does_not_fit_a_capella_header() {
+    let spec = spec();
+    let attested_slot = slot_of(spec.capella) + 2 * SLOTS_PER_EPOCH;
+    let update = period_update_with(attested_slot, Some(execution()));
+    assert_eq!(
+        update.to_ssz_bytes(&spec).unwrap_err(),
+        LightClientError::InvalidLightClientHeader
+    );
+}
+
+#[test]
+fn capella_containers_are_not_mistaken_for_altair_ones() {
+    let spec = spec();
+    // An optimistic update nobody signed, its offset is followed by zeroes and reads as an early
+    // slot when taken for the slot of an Altair header.
+    let fixed_size = 4 + SYNC_COMMITTEE_SIZE / 8 + SIGNATURE_SIZE + 8;
+    let mut bytes = vec![0; fixed_size];
+    bytes[..4].copy_from_slice(&(fixed_size as u32).to_le_bytes());
+    bytes.extend_from_slice(&slot_of(spec.capella).to_le_bytes());
+    bytes.resize(fixed_size + 112, 0);
+    assert_eq!(
+        fork_of_encoded_header(&bytes, HeaderContainer::OptimisticUpdate, &spec),
+        Ok(ForkName::Capella)
+    );
+
+    // Read as the fixed part of a bigger container the offset can't be right, and the bytes are
+    // too long for an Altair container.
+    assert_eq!(
+        fork_of_encoded_header(&bytes, HeaderContainer::FinalityUpdate, &spec),
+        Err(LightClientError::Decode { field: "header" })
+    );
+
+    let mut bytes = slot_of(spec.altair).to_le_bytes().to_vec();
+    bytes.resize(112 + SYNC_COMMITTEE_SIZE / 8 + SIGNATURE_SIZE + 8, 0);
+    assert_eq!(
+        fork_of_encoded_header(&bytes, HeaderContainer::OptimisticUpdate, &spec),
+        Ok(ForkName::Altair)
+    );
+}
+
+#[test]
+fn empty_execution_payloads_are_the_ones_headers_before_capella_carry() {
+    assert!(ExecutionPayloadHeader::default().is_empty());
+    let decoded_empty = ExecutionPayloadHeader {
+        logs_bloom: vec![0; BYTES_PER_LOGS_BLOOM],
+        ..Default::default()
+    };
+    assert!(decoded_empty.is_empty());
+    assert!(!ExecutionPayloadHeader {
+        block_number: 1,
+        ..decoded_empty
+    }
+    .is_empty());
+}
+
+#[test]
+fn bellatrix_headers_round_trip_through_capella_containers() {
+    let spec = spec();
+    let attested_slot = slot_of(spec.capella) + 2 * SLOTS_PER_EPOCH;
+    let bellatrix = header_with(slot_of(spec.bellatrix), None);
+    let bellatrix_bytes = bellatrix.to_ssz_bytes(&spec).unwrap();
+    let built_empty = LightClientHeader {
+        execution: Some(ExecutionPayloadHeader::default()),
+        execution_branch: vec![H256::default(); 4],
+        ..bellatrix.clone()
+    };
+    for finalized_header in [bellatrix, built_empty] {
+        let update = SyncCommitteePeriodUpdate {
+            finalized_header,
+            ..period_update_with(attested_slot, Some(execution_capella()))
+        };
+        let bytes = update.to_ssz_bytes(&spec).unwrap();
+        let decoded = SyncCommitteePeriodUpdate::decode(&bytes, &spec).unwrap();
+
+        // The container gives the header an empty payload, which stands for none.
+        let finalized_header = &decoded.finalized_header;
+        assert!(finalized_header.execution.as_ref().unwrap().is_empty());
+        assert!(is_valid_light_client_header(finalized_header, &spec));
+        assert_eq!(
+            finalized_header.to_ssz_bytes(&spec).unwrap(),
+            bellatrix_bytes
+        );
+        assert_eq!(decoded.to_ssz_bytes(&spec).unwrap(), bytes);
+    }
+}
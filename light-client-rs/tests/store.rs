@@ -0,0 +1,112 @@
+//! Forcing the best update through once the update timeout has elapsed.
+
+use bacon::store::validate_light_client_update;
+use bacon::*;
+
+mod common;
+use common::*;
+
+fn store_with_best(best_valid_update: SyncCommitteePeriodUpdate) -> LightClientStore {
+    let mut store = LightClientStore::new(header(1), sync_committee()).unwrap();
+    store.best_valid_update = Some(best_valid_update);
+    store
+}
+
+#[test]
+fn forced_updates_are_applied_after_the_timeout() {
+    let update = period_update(3 * SLOTS_PER_EPOCH, SUPERMAJORITY - 1);
+    let mut store = store_with_best(update.clone());
+
+    process_light_client_store_force_update(&mut store, UPDATE_TIMEOUT).unwrap();
+    assert!(store.best_valid_update.is_some());
+
+    process_light_client_store_force_update(&mut store, UPDATE_TIMEOUT + 2).unwrap();
+    assert!(store.best_valid_update.is_none());
+    assert_eq!(store.finalized_header, update.finalized_header);
+    let next_sync_committee = store
+        .next_sync_committee
+        .as_ref()
+        .map(|committee| committee.sync_committee());
+    assert_eq!(next_sync_committee, Some(&update.next_sync_committee));
+}
+
+#[test]
+fn failed_forced_updates_are_kept_for_a_retry() {
+    // Without a known next sync committee the store can't move on to the next period.
+    let update = period_update(PERIOD + 3 * SLOTS_PER_EPOCH, SUPERMAJORITY - 1);
+    let mut store = store_with_best(update.clone());
+
+    assert!(matches!(
+        process_light_client_store_force_update(&mut store, PERIOD + UPDATE_TIMEOUT),
+        Err(LightClientError::InvalidSyncCommitteePeriod { .. })
+    ));
+    let kept = store
+        .best_valid_update
+        .as_ref()
+        .map(|best| &best.attested_header);
+    assert_eq!(kept, Some(&update.attested_header));
+    assert_eq!(store.finalized_header, header(1));
+}
+
+#[test]
+fn committees_are_prepared_when_the_store_learns_them() {
+    let mut sync_committee = sync_committee();
+    sync_committee.pubkeys[1] = PublicKey([0; PUBKEY_SIZE]);
+    assert!(matches!(
+        LightClientStore::<DefaultBlsVerifier>::new(header(1), sync_committee.clone()),
+        Err(LightClientError::Bls(_))
+    ));
+
+    let update = SyncCommitteePeriodUpdate {
+        next_sync_committee: sync_committee,
+        ..period_update(3 * SLOTS_PER_EPOCH, SUPERMAJORITY - 1)
+    };
+    let mut store = store_with_best(update);
+    assert!(matches!(
+        process_light_client_store_force_update(&mut store, UPDATE_TIMEOUT + 2),
+        Err(LightClientError::Bls(_))
+    ));
+    assert!(store.next_sync_committee.is_none());
+}
+
+#[test]
+fn decoded_capella_updates_without_finality_reach_the_signature() {
+    // Capella a few epochs after genesis, so that the genesis header is from before it.
+    let mut spec = altair_spec();
+    spec.capella.epoch = 2;
+    let store: LightClientStore = LightClientStore::new(header(1), sync_committee()).unwrap();
+    let attested_slot = slot_of(spec.capella);
+    let empty_committee = SyncCommittee {
+        pubkeys: vec![PublicKey([0; PUBKEY_SIZE]); SYNC_COMMITTEE_SIZE],
+        aggregate_pubkey: PublicKey([0; PUBKEY_SIZE]),
+    };
+    let update = SyncCommitteePeriodUpdate {
+        attested_header: header_committing_to::<DefaultHasher>(
+            attested_slot,
+            execution(0),
+            CAPELLA_EXECUTION_ROOT,
+        ),
+        next_sync_committee: empty_committee,
+        finalized_header: LightClientHeader::default(),
+        ..period_update(attested_slot, SUPERMAJORITY)
+    };
+    let update = SyncCommitteePeriodUpdate {
+        next_sync_committee_branch: vec![H256::default(); update.next_sync_committee_branch.len()],
+        finality_branch: vec![H256::default(); update.finality_branch.len()],
+        ..update
+    };
+
+    // The absent finalized header decodes with the empty payload of the Capella container.
+    let update = through_ssz(&update, &spec);
+    assert!(update.finalized_header.execution.is_some());
+    assert!(matches!(
+        validate_light_client_update(
+            &store,
+            &update,
+            update.signature_slot,
+            &VerificationPolicy::spec(),
+            &spec
+        ),
+        Err(LightClientError::Bls(_))
+    ));
+}
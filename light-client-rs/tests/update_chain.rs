@@ -0,0 +1,171 @@
+//! A chain is only as good as its first bad update, which the error points at. Every chain is
+//! verified with both BLS backends, decoded and in place.
+
+use bacon::*;
+
+mod common;
+use common::*;
+
+// The trusted update of the first period, only its slots and next sync committee are used.
+fn checkpoint() -> SyncCommitteePeriodUpdate {
+    period_update(3 * SLOTS_PER_EPOCH, 0)
+}
+
+// The update of `period`, finalizing a header of the same period.
+fn update(period: u64) -> SyncCommitteePeriodUpdate {
+    signed_period_update(
+        &altair_spec(),
+        period * PERIOD + 3 * SLOTS_PER_EPOCH,
+        SUPERMAJORITY,
+    )
+}
+
+fn process(
+    updates: Vec<SyncCommitteePeriodUpdate>,
+) -> Result<(SyncCommittee, LightClientHeader), UpdateChainError> {
+    let spec = altair_spec();
+    let policy = VerificationPolicy::spec();
+    let checkpoint = checkpoint();
+    let blst = process_sync_committee_update_chain::<BlstVerifier, _>(
+        checkpoint.clone(),
+        updates.clone(),
+        &policy,
+        &spec,
+    );
+
+    // Both backends and the encoded chain have to come to the same result.
+    let milagro = process_sync_committee_update_chain::<MilagroVerifier, _>(
+        checkpoint.clone(),
+        updates.clone(),
+        &policy,
+        &spec,
+    );
+    assert_eq!(milagro, blst);
+    let encoded = updates
+        .iter()
+        .map(|update| update.to_ssz_bytes(&spec).unwrap())
+        .collect::<Vec<_>>();
+    let encoded = encoded.iter().map(Vec::as_slice).collect::<Vec<_>>();
+    let checkpoint = checkpoint.to_ssz_bytes(&spec).unwrap();
+    let ssz = ssz_process_sync_committee_update_chain(&checkpoint, &encoded, &policy, &spec);
+    assert_eq!(
+        ssz.map(|(sync_committee, finalized_header, _)| (sync_committee, finalized_header)),
+        blst
+    );
+    let view = process_sync_committee_update_chain_view::<MilagroVerifier, _>(
+        SyncCommitteePeriodUpdateView::new(&checkpoint, &spec).unwrap(),
+        encoded
+            .iter()
+            .map(|update| SyncCommitteePeriodUpdateView::new(update, &spec).unwrap()),
+        &policy,
+        &spec,
+    );
+    assert_eq!(
+        view.map(|(sync_committee, finalized_header)| (sync_committee.into(), finalized_header)),
+        blst
+    );
+    blst
+}
+
+#[test]
+fn chains_end_at_the_last_update() {
+    let last = update(3);
+    assert_eq!(
+        process(vec![update(1), update(2), last.clone()]),
+        Ok((last.next_sync_committee, last.finalized_header))
+    );
+}
+
+#[test]
+fn empty_chains_end_at_the_checkpoint() {
+    let checkpoint = checkpoint();
+    assert_eq!(
+        process(Vec::new()),
+        Ok((checkpoint.next_sync_committee, checkpoint.finalized_header))
+    );
+}
+
+#[test]
+fn updates_out_of_period_sequence_are_rejected() {
+    assert_eq!(
+        process(vec![update(1), update(3)]),
+        Err(UpdateChainError {
+            index: Some(1),
+            error: LightClientError::InvalidSyncCommitteePeriod {
+                store_period: 1,
+                update_period: 3,
+            },
+        })
+    );
+    // Signed by the right committee, but attested before the period it would prove.
+    let stale = signed_period_update(&altair_spec(), 2 * PERIOD - 1, SUPERMAJORITY);
+    assert_eq!(
+        process(vec![update(1), stale]),
+        Err(UpdateChainError {
+            index: Some(1),
+            error: LightClientError::InvalidSyncCommitteePeriod {
+                store_period: 1,
+                update_period: 1,
+            },
+        })
+    );
+    assert_eq!(
+        process(vec![update(2)]),
+        Err(UpdateChainError {
+            index: Some(0),
+            error: LightClientError::InvalidSyncCommitteePeriod {
+                store_period: 0,
+                update_period: 2,
+            },
+        })
+    );
+}
+
+#[test]
+fn chains_whose_finalized_slot_stands_still_are_rejected() {
+    let first = update(1);
+    let stalled = SyncCommitteePeriodUpdate {
+        finalized_header: first.finalized_header.clone(),
+        ..update(2)
+    };
+    assert_eq!(
+        process(vec![first, stalled]),
+        Err(UpdateChainError {
+            index: Some(1),
+            error: LightClientError::InvalidSlotOrder,
+        })
+    );
+}
+
+#[test]
+fn errors_name_the_failing_update_or_the_checkpoint() {
+    let mut unsigned = update(2);
+    unsigned.sync_aggregate.sync_committee_signature =
+        update(1).sync_aggregate.sync_committee_signature;
+    assert_eq!(
+        process(vec![update(1), unsigned, update(3)]),
+        Err(UpdateChainError {
+            index: Some(1),
+            error: LightClientError::Bls(BlsError::VerificationFailed),
+        })
+    );
+
+    // Only a checkpoint that can't be read fails on its own.
+    let spec = altair_spec();
+    let policy = VerificationPolicy::spec();
+    let update = update(1).to_ssz_bytes(&spec).unwrap();
+    let checkpoint = checkpoint().to_ssz_bytes(&spec).unwrap();
+    let truncated = &checkpoint[..checkpoint.len() - 1];
+    assert_eq!(
+        ssz_process_sync_committee_update_chain(truncated, &[&update], &policy, &spec)
+            .unwrap_err()
+            .index,
+        None
+    );
+    assert_eq!(
+        ssz_process_sync_committee_update_chain(&checkpoint, &[&update, truncated], &policy, &spec)
+            .unwrap_err()
+            .index,
+        Some(1)
+    );
+}
@@ -0,0 +1,85 @@
+//! Each criterion of the ranking decides between two updates that only differ in it and the ones
+//! ranked after it.
+
+use bacon::*;
+
+mod common;
+use common::*;
+
+// An update of a period well after genesis, so that every criterion can move it around.
+fn update(participants: usize) -> SyncCommitteePeriodUpdate {
+    period_update(10 * PERIOD + 3 * SLOTS_PER_EPOCH, participants)
+}
+
+fn without_finality(mut update: SyncCommitteePeriodUpdate) -> SyncCommitteePeriodUpdate {
+    update.finality_branch = vec![H256::default(); update.finality_branch.len()];
+    update
+}
+
+fn without_sync_committee(mut update: SyncCommitteePeriodUpdate) -> SyncCommitteePeriodUpdate {
+    update.next_sync_committee_branch =
+        vec![H256::default(); update.next_sync_committee_branch.len()];
+    update
+}
+
+fn assert_ranked(better: &SyncCommitteePeriodUpdate, worse: &SyncCommitteePeriodUpdate) {
+    assert!(is_better_update(better, worse));
+    assert!(!is_better_update(worse, better));
+}
+
+#[test]
+fn supermajority_comes_first() {
+    let without_anything = without_sync_committee(without_finality(update(SUPERMAJORITY)));
+    assert_ranked(&without_anything, &update(SUPERMAJORITY - 1));
+}
+
+#[test]
+fn participation_decides_between_minorities() {
+    assert_ranked(&update(SUPERMAJORITY - 1), &update(SUPERMAJORITY - 2));
+    assert_ranked(&update(2), &without_finality(update(1)));
+}
+
+#[test]
+fn relevant_sync_committee_beats_finality() {
+    assert_ranked(
+        &without_finality(update(SUPERMAJORITY)),
+        &without_sync_committee(update(SYNC_COMMITTEE_SIZE)),
+    );
+
+    // Signed in the next period, the committee isn't the one the signature needs.
+    let mut signed_later = update(SYNC_COMMITTEE_SIZE);
+    signed_later.signature_slot += PERIOD;
+    assert_ranked(&update(SUPERMAJORITY), &signed_later);
+}
+
+#[test]
+fn finality_comes_next() {
+    assert_ranked(
+        &update(SUPERMAJORITY),
+        &without_finality(update(SYNC_COMMITTEE_SIZE)),
+    );
+
+    // Finalizing a header of the previous period doesn't finalize the committee.
+    let mut previous_period = update(SYNC_COMMITTEE_SIZE);
+    previous_period.finalized_header = header(previous_period.attested_header.beacon.slot - PERIOD);
+    assert_ranked(&update(SUPERMAJORITY), &previous_period);
+}
+
+#[test]
+fn ties_go_to_participation_then_older_updates() {
+    assert_ranked(&update(SUPERMAJORITY + 1), &update(SUPERMAJORITY));
+
+    let mut later = update(SUPERMAJORITY);
+    later.attested_header = header(later.attested_header.beacon.slot + 1);
+    later.signature_slot -= 1;
+    assert_ranked(&update(SUPERMAJORITY), &later);
+
+    let mut signed_later = update(SUPERMAJORITY);
+    signed_later.signature_slot += 1;
+    assert_ranked(&update(SUPERMAJORITY), &signed_later);
+
+    assert!(!is_better_update(
+        &update(SUPERMAJORITY),
+        &update(SUPERMAJORITY)
+    ));
+}
@@ -0,0 +1,130 @@
+//! The policy is checked before anything else, the updates that get past it here are stopped by
+//! a later check so that they never reach the signature.
+
+use bacon::store::validate_light_client_update;
+use bacon::*;
+
+mod common;
+use common::*;
+
+const STORE_SLOT: u64 = SLOTS_PER_EPOCH;
+const SIGNATURE_SLOT: u64 = 4 * SLOTS_PER_EPOCH + 1;
+
+fn store() -> LightClientStore {
+    LightClientStore::new(header(STORE_SLOT), sync_committee()).unwrap()
+}
+
+// Signed in the store's period, and carrying a finalized header without the branch proving it.
+fn store_update(participants: usize) -> SyncCommitteePeriodUpdate {
+    let update = period_update(SIGNATURE_SLOT - 1, participants);
+    SyncCommitteePeriodUpdate {
+        finality_branch: vec![H256::default(); update.finality_branch.len()],
+        next_sync_committee_branch: vec![H256::default(); update.next_sync_committee_branch.len()],
+        ..update
+    }
+}
+
+#[test]
+fn spec_policy_only_finalizes_with_a_supermajority() {
+    let policy = VerificationPolicy::spec();
+    let supermajority = SUPERMAJORITY as u64;
+    assert_eq!(policy, VerificationPolicy::default());
+    assert!(policy.is_finalizing(supermajority));
+    assert!(!policy.is_finalizing(supermajority - 1));
+    assert_eq!(
+        policy.verify_finality_participation(supermajority - 1),
+        Err(LightClientError::InsufficientParticipation {
+            participants: supermajority - 1,
+            required: supermajority,
+        })
+    );
+    policy
+        .verify_participation(MIN_SYNC_COMMITTEE_PARTICIPANTS)
+        .unwrap();
+    policy.verify_update_age(SIGNATURE_SLOT, u64::MAX).unwrap();
+}
+
+#[test]
+fn store_updates_need_the_minimum_participants() {
+    let spec = altair_spec();
+    let store = store();
+    let validate = |update: &SyncCommitteePeriodUpdate, policy: &VerificationPolicy| {
+        validate_light_client_update(&store, update, SIGNATURE_SLOT, policy, &spec)
+    };
+
+    // A single signer is enough for the spec, finality is only decided when applying the update.
+    assert_eq!(
+        validate(&store_update(1), &VerificationPolicy::spec()),
+        Err(LightClientError::UnexpectedFinalizedHeader)
+    );
+    let quarter = SYNC_COMMITTEE_SIZE / 4;
+    let policy = VerificationPolicy {
+        min_participants: quarter as u64,
+        ..VerificationPolicy::spec()
+    };
+    assert_eq!(
+        validate(&store_update(quarter - 1), &policy),
+        Err(LightClientError::InsufficientParticipation {
+            participants: quarter as u64 - 1,
+            required: quarter as u64,
+        })
+    );
+}
+
+#[test]
+fn store_updates_expire_once_a_maximum_age_is_set() {
+    let spec = altair_spec();
+    let store = store();
+    let policy = VerificationPolicy {
+        max_update_age: Some(SLOTS_PER_EPOCH),
+        ..VerificationPolicy::spec()
+    };
+    let validate = |current_slot| {
+        validate_light_client_update(&store, &store_update(1), current_slot, &policy, &spec)
+    };
+
+    assert_eq!(
+        validate(SIGNATURE_SLOT + SLOTS_PER_EPOCH),
+        Err(LightClientError::UnexpectedFinalizedHeader)
+    );
+    assert_eq!(
+        validate(SIGNATURE_SLOT + SLOTS_PER_EPOCH + 1),
+        Err(LightClientError::StaleUpdate {
+            signature_slot: SIGNATURE_SLOT,
+            current_slot: SIGNATURE_SLOT + SLOTS_PER_EPOCH + 1,
+        })
+    );
+    assert_eq!(
+        validate(SIGNATURE_SLOT - 1),
+        Err(LightClientError::InvalidSlotOrder)
+    );
+}
+
+#[test]
+fn period_updates_need_the_finality_threshold() {
+    let spec = altair_spec();
+    let policy = VerificationPolicy {
+        finality_threshold: SYNC_COMMITTEE_SIZE as u64,
+        ..VerificationPolicy::spec()
+    };
+    let process = |participants| {
+        process_sync_committee_period_update::<DefaultBlsVerifier, _>(
+            period_update(3 * SLOTS_PER_EPOCH, SYNC_COMMITTEE_SIZE),
+            period_update(PERIOD + 3 * SLOTS_PER_EPOCH, participants),
+            &policy,
+            &spec,
+        )
+    };
+
+    assert_eq!(
+        process(SYNC_COMMITTEE_SIZE - 1).unwrap_err(),
+        LightClientError::InsufficientParticipation {
+            participants: SYNC_COMMITTEE_SIZE as u64 - 1,
+            required: SYNC_COMMITTEE_SIZE as u64,
+        }
+    );
+    assert_eq!(
+        process(SYNC_COMMITTEE_SIZE).unwrap_err(),
+        LightClientError::InvalidMerkleBranch(MerkleBranch::NextSyncCommittee)
+    );
+}
@@ -0,0 +1,212 @@
+//! The views read the same update as the decoder does, and refuse the same malformed bytes.
+
+use bacon::*;
+
+mod common;
+use common::*;
+
+// A spec whose updates are all of `fork`.
+fn spec_of(fork: ForkName) -> ChainSpec {
+    let mut spec = ChainSpec::devnet(H256([7; 32]), 0);
+    let later_forks = [
+        (ForkName::Capella, &mut spec.capella),
+        (ForkName::Deneb, &mut spec.deneb),
+        (ForkName::Electra, &mut spec.electra),
+    ];
+    for (later_fork, activation) in later_forks {
+        if later_fork > fork {
+            activation.epoch = chain_spec::FAR_FUTURE_EPOCH;
+        }
+    }
+    spec
+}
+
+const FORKS: [ForkName; 4] = [
+    ForkName::Bellatrix,
+    ForkName::Capella,
+    ForkName::Deneb,
+    ForkName::Electra,
+];
+
+// Only the slots and the next sync committee of the previous update are used.
+fn prev_update(spec: &ChainSpec) -> SyncCommitteePeriodUpdate {
+    signed_period_update(spec, 3 * SLOTS_PER_EPOCH, 0)
+}
+
+fn update(spec: &ChainSpec) -> SyncCommitteePeriodUpdate {
+    signed_period_update(spec, PERIOD + 3 * SLOTS_PER_EPOCH, SUPERMAJORITY)
+}
+
+// Reads every field, so that a view that only fails on access counts as rejected.
+fn read(bytes: &[u8], spec: &ChainSpec) -> Result<(), LightClientError> {
+    let view = SyncCommitteePeriodUpdateView::new(bytes, spec)?;
+    view.attested_header()?;
+    view.finalized_header()?;
+    Ok(())
+}
+
+// From Capella on the encoding ends in the extra data of the finalized header's execution
+// payload, a list that can be cut or extended. Both readers have to reject the changed bytes
+// or both have to read a finalized header that no longer verifies.
+fn assert_rejected_or_changed(
+    bytes: &[u8],
+    original: &SyncCommitteePeriodUpdate,
+    spec: &ChainSpec,
+) {
+    match SyncCommitteePeriodUpdate::decode(bytes, spec) {
+        Ok(decoded) => {
+            assert!(spec.fork_at_slot(original.attested_header.beacon.slot) >= ForkName::Capella);
+            let view = SyncCommitteePeriodUpdateView::new(bytes, spec).unwrap();
+            assert_eq!(view.finalized_header().unwrap(), decoded.finalized_header);
+            assert_ne!(decoded.finalized_header, original.finalized_header);
+        }
+        Err(_) => assert!(read(bytes, spec).is_err()),
+    }
+}
+
+fn offset(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+}
+
+fn with_offset(bytes: &[u8], at: usize, offset: u32) -> Vec<u8> {
+    let mut bytes = bytes.to_vec();
+    bytes[at..at + 4].copy_from_slice(&offset.to_le_bytes());
+    bytes
+}
+
+#[test]
+fn views_read_what_the_decoder_decodes() {
+    for fork in FORKS {
+        let spec = spec_of(fork);
+        let bytes = update(&spec).to_ssz_bytes(&spec).unwrap();
+        let decoded = SyncCommitteePeriodUpdate::decode(&bytes, &spec).unwrap();
+        let view = SyncCommitteePeriodUpdateView::new(&bytes, &spec).unwrap();
+
+        assert_eq!(view.fork(), fork);
+        assert_eq!(view.attested_header().unwrap(), decoded.attested_header);
+        assert_eq!(
+            SyncCommittee::from(view.next_sync_committee()),
+            decoded.next_sync_committee
+        );
+        assert_eq!(
+            view.next_sync_committee().hash_tree_root().unwrap(),
+            decoded.next_sync_committee.hash_tree_root().unwrap().0
+        );
+        assert_eq!(
+            view.next_sync_committee_branch().collect::<Vec<_>>(),
+            decoded.next_sync_committee_branch
+        );
+        assert_eq!(view.finalized_header().unwrap(), decoded.finalized_header);
+        assert_eq!(
+            view.finality_branch().collect::<Vec<_>>(),
+            decoded.finality_branch
+        );
+        let view_bits = (0..SYNC_COMMITTEE_SIZE)
+            .map(|index| (view.sync_committee_bits()[index / 8] >> (index % 8)) & 1 == 1)
+            .collect::<Vec<_>>();
+        let decoded_bits = decoded
+            .sync_aggregate
+            .sync_committee_bits
+            .iter()
+            .collect::<Vec<_>>();
+        assert_eq!(view_bits, decoded_bits);
+        assert_eq!(
+            view.sync_committee_signature(),
+            decoded.sync_aggregate.sync_committee_signature
+        );
+        assert_eq!(view.signature_slot(), decoded.signature_slot);
+    }
+}
+
+#[test]
+fn views_verify_like_decoded_updates() {
+    for fork in FORKS {
+        let spec = spec_of(fork);
+        let policy = VerificationPolicy::spec();
+        let mut tampered = update(&spec);
+        tampered.finality_branch[0] = H256([13; 32]);
+        let mut unsigned = update(&spec);
+        unsigned.sync_aggregate.sync_committee_bits = bits(|_| true);
+
+        let cases = [(update(&spec), true), (tampered, false), (unsigned, false)];
+        for (update, valid) in cases {
+            let prev_bytes = prev_update(&spec).to_ssz_bytes(&spec).unwrap();
+            let bytes = update.to_ssz_bytes(&spec).unwrap();
+            let view = process_sync_committee_period_update_view::<DefaultBlsVerifier, _>(
+                &SyncCommitteePeriodUpdateView::new(&prev_bytes, &spec).unwrap(),
+                &SyncCommitteePeriodUpdateView::new(&bytes, &spec).unwrap(),
+                &policy,
+                &spec,
+            )
+            .map(|(sync_committee, finalized_header)| (sync_committee.into(), finalized_header));
+            let decoded = process_sync_committee_period_update::<DefaultBlsVerifier, _>(
+                prev_update(&spec),
+                SyncCommitteePeriodUpdate::decode(&bytes, &spec).unwrap(),
+                &policy,
+                &spec,
+            );
+            assert_eq!(decoded.is_ok(), valid);
+            assert_eq!(view, decoded);
+        }
+    }
+}
+
+#[test]
+fn truncated_updates_are_rejected() {
+    for fork in FORKS {
+        let spec = spec_of(fork);
+        let update = update(&spec);
+        let bytes = update.to_ssz_bytes(&spec).unwrap();
+        read(&bytes, &spec).unwrap();
+        for length in 0..bytes.len() {
+            assert_rejected_or_changed(&bytes[..length], &update, &spec);
+        }
+    }
+}
+
+#[test]
+fn trailing_bytes_are_rejected() {
+    for fork in FORKS {
+        let spec = spec_of(fork);
+        let update = update(&spec);
+        let mut bytes = update.to_ssz_bytes(&spec).unwrap();
+        bytes.push(0);
+        assert_rejected_or_changed(&bytes, &update, &spec);
+    }
+}
+
+#[test]
+fn bad_header_offsets_are_rejected() {
+    for fork in [ForkName::Capella, ForkName::Deneb, ForkName::Electra] {
+        let spec = spec_of(fork);
+        let bytes = update(&spec).to_ssz_bytes(&spec).unwrap();
+        let branch_depth = if fork >= ForkName::Electra {
+            NEXT_SYNC_COMMITTEE_DEPTH_ELECTRA
+        } else {
+            NEXT_SYNC_COMMITTEE_DEPTH
+        };
+        // The attested header's offset comes first, the finalized header's after the committee
+        // and its branch.
+        let finalized_at = 4 + SyncCommitteeView::SSZ_SIZE + 32 * branch_depth as usize;
+        let attested_offset = offset(&bytes, 0);
+        let finalized_offset = offset(&bytes, finalized_at);
+        assert_eq!(
+            finalized_offset as usize,
+            bytes.len() - (bytes.len() - attested_offset as usize) / 2
+        );
+
+        let bad_offsets = [
+            with_offset(&bytes, 0, attested_offset - 1),
+            with_offset(&bytes, 0, attested_offset + 1),
+            with_offset(&bytes, finalized_at, attested_offset - 1),
+            with_offset(&bytes, finalized_at, finalized_offset - 1),
+            with_offset(&bytes, finalized_at, finalized_offset + 1),
+            with_offset(&bytes, finalized_at, bytes.len() as u32 + 1),
+            with_offset(&bytes, finalized_at, u32::MAX),
+        ];
+        for (index, bytes) in bad_offsets.iter().enumerate() {
+            assert!(read(bytes, &spec).is_err(), "{fork:?}, offset {index}");
+            assert!(SyncCommitteePeriodUpdate::decode(bytes, &spec).is_err());
+        }
+    }
+}
@@ -5,39 +5,79 @@ use std::io::Read;
 use std::io::Write;
 
 const PREIMAGE_CACHE_DIR: &str = "../preimage-cache";
-
-const VALIDATORS_ROOT_HEX_STR: &str =
-    "4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95";
+const USAGE: &str = "usage: shortcut-rs [--network mainnet|sepolia|holesky] <checkpoint> [<update>...]";
 
 fn main() -> Result<(), Box<dyn Error>> {
 
-    let args: Vec<String> = env::args().collect();
+    let (network, hashes) = parse_args(env::args().skip(1))?;
+    let spec = chain_spec(&network)?;
 
-    let prev_update = load_hash(&args[1]);
-    let update = load_hash(&args[2]);
+    // the first hash is the trusted checkpoint, every following one an update of the next period
+    let (checkpoint, updates) = hashes.split_first().ok_or(USAGE)?;
+    let checkpoint = load_hash(checkpoint);
+    let updates: Vec<Vec<u8>> = updates.iter().map(|hash| load_hash(hash)).collect();
+    let updates: Vec<&[u8]> = updates.iter().map(|update| update.as_slice()).collect();
 
-    let validators_root = bacon::H256(
-        hex::decode(VALIDATORS_ROOT_HEX_STR)
-            .unwrap()
-            .try_into()
-            .unwrap(),
-    );
-    let (_sync_committee, _beacon_header) =
-        bacon::ssz_process_sync_committee_period_update(&prev_update, &update, validators_root)?;
+    let policy = bacon::VerificationPolicy::spec();
+    let (_sync_committee, _beacon_header, _block_root) =
+        bacon::ssz_process_sync_committee_update_chain(&checkpoint, &updates, &policy, &spec)?;
 
     // println!("{:?}, {:?}", sync_committee, beacon_header);
     let stdout = std::io::stdout();
     let mut stdout_lock = stdout.lock();
 
     // write 64 bytes to std-out for testing purposes
-    stdout_lock.write_all(&hex::decode(VALIDATORS_ROOT_HEX_STR).unwrap())?;
-    stdout_lock.write_all(&hex::decode(VALIDATORS_ROOT_HEX_STR).unwrap())?;
+    stdout_lock.write_all(spec.genesis_validators_root.as_bytes())?;
+    stdout_lock.write_all(spec.genesis_validators_root.as_bytes())?;
 
     //println!("Validation success!!");
 
     Ok(())
 }
 
+// Splits the arguments into the network, mainnet unless given, and the preimage hashes.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<(String, Vec<String>), Box<dyn Error>> {
+    let mut network = String::from("mainnet");
+    let mut hashes = Vec::new();
+    while let Some(arg) = args.next() {
+        if arg == "--network" {
+            network = args.next().ok_or(USAGE)?;
+        } else if let Some(name) = arg.strip_prefix("--network=") {
+            network = name.to_string();
+        } else if arg.starts_with("--") {
+            return Err(format!("unknown option {}, {}", arg, USAGE).into());
+        } else {
+            hashes.push(arg);
+        }
+    }
+    Ok((network, hashes))
+}
+
+// The networks bacon knows all run the mainnet preset, which changes the size of the encoded
+// updates, so a binary built for the minimal preset can't verify any of them.
+fn chain_spec(network: &str) -> Result<bacon::ChainSpec, Box<dyn Error>> {
+    let spec = match network {
+        "mainnet" => bacon::ChainSpec::mainnet(),
+        "sepolia" => bacon::ChainSpec::sepolia(),
+        "holesky" => bacon::ChainSpec::holesky(),
+        _ => {
+            return Err(format!(
+                "unknown network {}, expected mainnet, sepolia or holesky",
+                network
+            )
+            .into())
+        }
+    };
+    if bacon::IS_MINIMAL {
+        return Err(format!(
+            "{} uses the mainnet preset, build with the `mainnet` feature to verify its updates",
+            network
+        )
+        .into());
+    }
+    Ok(spec)
+}
+
 fn load_hash(hash: &str) -> Vec<u8> {
     let mut f = File::open(format!("{}/{}", PREIMAGE_CACHE_DIR, hash)).unwrap();
     let mut buffer = Vec::new();
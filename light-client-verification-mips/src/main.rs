@@ -14,12 +14,8 @@ extern crate rlibc; // memcpy, and friends
 mod heap;
 mod iommu;
 
-pub type H256 = [u8; 32];
-
-const VALIDATORS_ROOT: H256 = [
-    75, 54, 61, 185, 78, 40, 97, 32, 215, 110, 185, 5, 52, 15, 221, 78, 84, 191, 233, 240, 107,
-    243, 63, 246, 207, 90, 210, 127, 81, 27, 254, 149,
-];
+const SPEC: bacon::ChainSpec = bacon::ChainSpec::mainnet();
+const POLICY: bacon::VerificationPolicy = bacon::VerificationPolicy::spec();
 
 /// Main entrypoint.
 #[no_mangle]
@@ -36,7 +32,8 @@ pub extern "C" fn _start() {
     match bacon::ssz_process_sync_committee_period_update(
         prev_update_bytes,
         current_update_bytes,
-        bacon::H256(VALIDATORS_ROOT),
+        &POLICY,
+        &SPEC,
     ) {
         Ok(_) => {
             iommu::output([0xff_u8; 32]);